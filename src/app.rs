@@ -1,12 +1,17 @@
 use crate::{config::Config, pages};
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use enum_dispatch::enum_dispatch;
-use ratatui::{layout::Rect, Frame};
+use ratatui::{
+    layout::{Position, Rect},
+    Frame,
+};
 
 #[derive(Debug)]
 pub struct App {
     pub running: bool,
     pub current_page: Page,
+    /// The page is hidden when the terminal is too small for it, it can't be clicked then.
+    pub is_page_shown: bool,
 }
 
 impl Default for App {
@@ -14,6 +19,7 @@ impl Default for App {
         Self {
             running: true,
             current_page: Page::Homepage(pages::Homepage::default()),
+            is_page_shown: true,
         }
     }
 }
@@ -23,15 +29,28 @@ impl App {
         Self::default()
     }
 
+    /// Start on another page than the homepage.
+    pub fn with_page(page: Page) -> Self {
+        Self {
+            running: true,
+            current_page: page,
+            is_page_shown: true,
+        }
+    }
+
     /// Handles the tick event of the terminal.
     pub fn tick(&mut self) {}
 
-    /// Set running to false to quit the application.
+    /// Set running to false to quit the application, the current page is told first.
     pub fn quit(&mut self) {
-        self.running = false;
+        if self.current_page.on_quit() {
+            self.running = false;
+        }
     }
 }
 
+// pages are only moved once, when navigating to them
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug)]
 pub enum PageEvent {
     Nothing,
@@ -45,13 +64,55 @@ pub trait IPage: std::fmt::Debug {
     fn render(&mut self, frame: &mut Frame, main_area: Rect, config: &Config);
     /// To update the content of the page with key events.
     fn handle_key_events(&mut self, key_event: KeyEvent, config: &mut Config) -> PageEvent;
+    /// To update the content of the page with mouse events, the wheel scrolls by default.
+    fn handle_mouse_events(&mut self, mouse_event: MouseEvent, config: &mut Config) -> PageEvent {
+        scroll_with_arrows(self, mouse_event, config)
+    }
+    /// Called when the terminal loses or gets back the focus (nothing by default).
+    fn handle_focus_change(&mut self, _is_focused: bool, _config: &Config) {}
+    /// Whether the page can be shown in a terminal smaller than the main box (no by default).
+    fn has_compact_layout(&self) -> bool {
+        false
+    }
+    /// Used on large terminals, to show more next to the page (nothing by default).
+    fn render_side_panels(&mut self, _frame: &mut Frame, _areas: [Rect; 2], _config: &Config) {}
+    /// Called when the application is quit while the page is shown, e.g. to save its state.
+    /// The application keeps running when it returns `false`, e.g. to ask something first.
+    fn on_quit(&mut self) -> bool {
+        true
+    }
 }
 
+// only one page exists at a time
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug)]
 #[enum_dispatch(IPage)]
 pub enum Page {
     Homepage(pages::Homepage),
     ConfigPage(pages::ConfigPage),
+    DeckPage(pages::DeckPage),
+    SelectionPage(pages::SelectionPage),
+    HistoryPage(pages::HistoryPage),
+    StatsPage(pages::StatsPage),
     StudyPage(pages::StudyPage),
     ResultPage(pages::ResultPage),
 }
+
+/// The mouse wheel does the same as the up and down arrows.
+pub fn scroll_with_arrows<P: IPage + ?Sized>(
+    page: &mut P,
+    mouse_event: MouseEvent,
+    config: &mut Config,
+) -> PageEvent {
+    match mouse_event.kind {
+        MouseEventKind::ScrollUp => page.handle_key_events(KeyEvent::from(KeyCode::Up), config),
+        MouseEventKind::ScrollDown => page.handle_key_events(KeyEvent::from(KeyCode::Down), config),
+        _ => PageEvent::Nothing,
+    }
+}
+
+/// Where the left button was pressed, if it was.
+pub fn left_click(mouse_event: &MouseEvent) -> Option<Position> {
+    (mouse_event.kind == MouseEventKind::Down(MouseButton::Left))
+        .then_some(Position::new(mouse_event.column, mouse_event.row))
+}
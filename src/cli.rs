@@ -0,0 +1,412 @@
+//! Command line interface, the app starts on the homepage when no subcommand is given.
+
+use crate::{
+    app::Page,
+    config::{Config, ConfigError, WritingSystem},
+    headless,
+    models::{
+        create_study_plan_with_rng,
+        deck::{Deck, DeckError},
+        export::{export_sessions, ExportError, ExportFormat},
+        history::{History, HistoryError},
+        kana::KanaRepresentation,
+        kana_group::KanaRow,
+        session::SessionSettings,
+        srs::{ReviewSchedule, ScheduleError},
+        stats::Stats,
+        word::{session_words, JlptLevel},
+        StudyPlan,
+    },
+    pages::StudyPage,
+    transliteration::{to_hiragana, to_katakana, to_romaji},
+};
+use rand::{rngs::SmallRng, seq::SliceRandom, SeedableRng};
+use std::{
+    io::BufRead,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use strum::IntoEnumIterator;
+
+#[derive(Debug, clap::Parser)]
+#[command(version, about = "Learn your kanas from the terminal.")]
+pub struct Cli {
+    /// Use this config file instead of the default one, with the history and decks of its folder.
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Command {
+    /// Start a study session right away.
+    Study(StudyArgs),
+    /// Study without the interface, one kana per line (works with piped input).
+    Quiz(StudyArgs),
+    /// Print the config, or change one of its values.
+    Config {
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
+    },
+    /// Print a summary of your statistics.
+    Stats,
+    /// Export the answers of every session, one row per answer.
+    Export {
+        /// File to write, the format is guessed from its extension (.json or .csv).
+        path: PathBuf,
+        #[arg(long, value_enum)]
+        format: Option<ExportFormat>,
+    },
+    /// Convert text between kana and Hepburn rōmaji, or between hiragana and katakana.
+    Convert {
+        /// Text to convert, the standard input is converted line by line when it is empty.
+        text: Vec<String>,
+        /// Script to convert to, rōmaji for text with kanas and hiragana otherwise.
+        #[arg(long, value_enum)]
+        to: Option<ConvertTarget>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum ConvertTarget {
+    Romaji,
+    Hiragana,
+    Katakana,
+}
+
+/// Options of the study session, they override the config without saving it.
+#[derive(Debug, clap::Args)]
+pub struct StudyArgs {
+    /// Script the kanas are shown in.
+    #[arg(long, value_enum)]
+    pub script: Option<WritingSystem>,
+    /// Rows of the kana table to study, separated by commas (e.g. `vowels,k,ky,extended-f`).
+    #[arg(long, value_delimiter = ',', value_parser = parse_row)]
+    pub rows: Vec<KanaRow>,
+    /// Maximum number of kanas to study.
+    #[arg(long)]
+    pub count: Option<usize>,
+    /// Seed of the shuffle, to study the kanas in the same order again.
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Study the cards of a deck file instead of kanas.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["script", "rows"])]
+    pub deck: Option<PathBuf>,
+    /// Study the bundled words of a JLPT level instead of kanas.
+    #[arg(long, value_enum, value_name = "LEVEL", conflicts_with_all = ["rows", "deck"])]
+    pub words: Option<JlptLevel>,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ConfigAction {
+    /// Change a value of the config, written in TOML (e.g. `set choices_count 6`).
+    Set { key: String, value: String },
+}
+
+#[derive(Debug)]
+pub enum CliError {
+    NothingToStudy,
+    UnknownConfigKey(String),
+    InvalidConfigValue(toml::de::Error),
+    Config(ConfigError),
+    History(HistoryError),
+    Schedule(ScheduleError),
+    Export(ExportError),
+    Deck(DeckError),
+    NotInQuiz,
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NothingToStudy => write!(f, "no kana to study, check your options"),
+            Self::UnknownConfigKey(key) => write!(f, "unknown config key: {}", key),
+            Self::InvalidConfigValue(e) => write!(f, "invalid config value: {}", e),
+            Self::Config(e) => write!(f, "{}", e),
+            Self::History(e) => write!(f, "{}", e),
+            Self::Schedule(e) => write!(f, "{}", e),
+            Self::Export(e) => write!(f, "{}", e),
+            Self::Deck(e) => write!(f, "{}", e),
+            Self::NotInQuiz => write!(f, "decks and words can only be studied in the app"),
+            Self::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+fn parse_row(name: &str) -> Result<KanaRow, String> {
+    KanaRow::from_str(name).map_err(|_| {
+        let names: Vec<String> = KanaRow::iter()
+            .map(|row| {
+                format!("{:?}", row)
+                    .to_lowercase()
+                    .replace("extended", "extended-")
+            })
+            .collect();
+        format!("unknown row, expected one of: {}", names.join(", "))
+    })
+}
+
+impl StudyArgs {
+    /// The config of the session: the options are applied to a copy of the config,
+    /// so they are never saved with it.
+    fn session_config(&self, config: &Config) -> Config {
+        let mut config = config.clone();
+        if let Some(script) = &self.script {
+            config.writing_system = script.clone();
+        }
+        if !self.rows.is_empty() {
+            config.kana_selection = self
+                .rows
+                .iter()
+                .flat_map(|row| row.kanas().iter().cloned())
+                .collect();
+        }
+        config
+    }
+
+    /// The study plan of the session, with the [session config](Self::session_config).
+    fn study_plan(&self, config: &Config) -> StudyPlan {
+        let mut plan = create_study_plan_with_rng(config, &mut self.rng());
+        if let Some(count) = self.count {
+            plan.truncate(count);
+        }
+
+        plan
+    }
+
+    /// The study page of the kanas, of the cards of the deck, or of the words.
+    pub fn study_page(&self, config: &Config) -> Result<Page, CliError> {
+        let config = self.session_config(config);
+        let study_page = match (&self.deck, self.words) {
+            (Some(deck_path), _) => {
+                let mut cards = Deck::load(deck_path).map_err(CliError::Deck)?.cards;
+                cards.shuffle(&mut self.rng());
+                if let Some(count) = self.count {
+                    cards.truncate(count);
+                }
+                StudyPage::from_cards(cards)
+            }
+            (None, Some(level)) => {
+                let mut words = session_words(level, &config.writing_system, &mut self.rng());
+                if let Some(count) = self.count {
+                    words.truncate(count);
+                }
+                StudyPage::from_words(words)
+            }
+            (None, None) => StudyPage::new(self.study_plan(&config)),
+        };
+        study_page
+            .map(|study_page| study_page.with_settings(SessionSettings::from(&config)))
+            .map(Page::from)
+            .ok_or(CliError::NothingToStudy)
+    }
+
+    fn rng(&self) -> SmallRng {
+        match self.seed {
+            Some(seed) => SmallRng::seed_from_u64(seed),
+            None => SmallRng::from_rng(&mut rand::rng()),
+        }
+    }
+}
+
+/// Run the line-oriented quiz on stdin and stdout, the session is saved like in the app.
+pub fn run_quiz(args: &StudyArgs, config: &Config) -> Result<(), CliError> {
+    if args.deck.is_some() || args.words.is_some() {
+        return Err(CliError::NotInQuiz);
+    }
+    let config = &args.session_config(config);
+    let plan = args.study_plan(config);
+    if plan.is_empty() {
+        return Err(CliError::NothingToStudy);
+    }
+    let record = headless::run_quiz(
+        plan,
+        config,
+        std::io::stdin().lock(),
+        std::io::stdout().lock(),
+    )
+    .map_err(CliError::Io)?;
+    if record.answers.is_empty() {
+        return Ok(());
+    }
+
+    ReviewSchedule::update_saved(&record.answers).map_err(CliError::Schedule)?;
+    History::append(record).map_err(CliError::History)
+}
+
+/// Print the config, or save it with the changed value.
+pub fn run_config(action: Option<ConfigAction>, config: &Config) -> Result<(), CliError> {
+    let Some(ConfigAction::Set { key, value }) = action else {
+        let toml = toml::to_string_pretty(config)
+            .map_err(|e| CliError::Config(ConfigError::SerializationError(e)))?;
+        print!("{}", toml);
+        return Ok(());
+    };
+
+    let mut table = toml::Table::try_from(config)
+        .map_err(|e| CliError::Config(ConfigError::SerializationError(e)))?;
+    if !table.contains_key(&key) {
+        return Err(CliError::UnknownConfigKey(key));
+    }
+    // values that are not valid TOML are taken as strings, e.g. `Katakana`
+    let value = toml::from_str::<toml::Table>(&format!("value = {}", value))
+        .ok()
+        .and_then(|mut parsed| parsed.remove("value"))
+        .unwrap_or(toml::Value::String(value));
+    table.insert(key, value);
+
+    let new_config: Config = table.try_into().map_err(CliError::InvalidConfigValue)?;
+    new_config.save().map_err(CliError::Config)
+}
+
+/// Export the whole history.
+pub fn export(path: &Path, format: Option<ExportFormat>) -> Result<(), CliError> {
+    let history = History::load().map_err(CliError::History)?;
+    export_sessions(&history.sessions, path, format).map_err(CliError::Export)?;
+    println!(
+        "Exported {} sessions to {}",
+        history.sessions.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Print the converted text, or every converted line of the standard input.
+pub fn convert(text: &[String], to: Option<ConvertTarget>) -> Result<(), CliError> {
+    if !text.is_empty() {
+        println!("{}", convert_text(&text.join(" "), to));
+        return Ok(());
+    }
+    for line in std::io::stdin().lock().lines() {
+        println!("{}", convert_text(&line.map_err(CliError::Io)?, to));
+    }
+    Ok(())
+}
+
+fn convert_text(text: &str, to: Option<ConvertTarget>) -> String {
+    let has_kanas = text
+        .chars()
+        .any(|c| matches!(c, '\u{3040}'..='\u{309F}' | '\u{30A0}'..='\u{30FF}'));
+    match to {
+        Some(ConvertTarget::Romaji) => to_romaji(text),
+        Some(ConvertTarget::Hiragana) => to_hiragana(text),
+        Some(ConvertTarget::Katakana) => to_katakana(text),
+        None if has_kanas => to_romaji(text),
+        None => to_hiragana(text),
+    }
+}
+
+/// Print the same overview as the stats page.
+pub fn print_stats(config: &Config) -> Result<(), CliError> {
+    let history = History::load().map_err(CliError::History)?;
+    let stats = Stats::from(&history);
+    let representation = KanaRepresentation::from(config.writing_system.clone());
+
+    let average_seconds = stats.average_elapsed_ms / 1000;
+    println!("Sessions: {}", stats.sessions_count);
+    println!("Accuracy: {:.0}%", stats.accuracy());
+    println!(
+        "Average time: {:02}:{:02}",
+        average_seconds / 60,
+        average_seconds % 60
+    );
+    println!("Most missed:");
+    let most_missed = stats.most_missed(5);
+    if most_missed.is_empty() {
+        println!("nothing, well done!");
+    }
+    for kana_stats in most_missed {
+        println!(
+            "{} {:<4} \u{2717} {}/{}",
+            kana_stats.kana.to_representation(&representation),
+            kana_stats.kana,
+            kana_stats.wrong_count(),
+            kana_stats.total_count
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{kana::Kana, StudyItem};
+    use clap::Parser;
+
+    fn study_args(args: &[&str]) -> StudyArgs {
+        let cli = Cli::try_parse_from([&["kana-tui", "study"], args].concat()).unwrap();
+        match cli.command {
+            Some(Command::Study(study_args)) => study_args,
+            _ => panic!("not the study subcommand"),
+        }
+    }
+
+    #[test]
+    fn parse_study_args() {
+        let args = study_args(&["--script", "both", "--rows", "vowels,ky,extended-f"]);
+        assert_eq!(args.script, Some(WritingSystem::Both));
+        assert_eq!(
+            args.rows,
+            Vec::from([KanaRow::Vowels, KanaRow::Ky, KanaRow::ExtendedF])
+        );
+        assert!(Cli::try_parse_from(["kana-tui", "study", "--rows", "x"]).is_err());
+
+        let args = study_args(&["--words", "n4", "--script", "katakana"]);
+        assert_eq!(args.words, Some(JlptLevel::N4));
+        assert!(
+            Cli::try_parse_from(["kana-tui", "study", "--words", "n5", "--rows", "k"]).is_err()
+        );
+    }
+
+    #[test]
+    fn convert_text_to_the_other_script() {
+        assert_eq!(convert_text("がっこう", None), "gakkou");
+        assert_eq!(convert_text("gakkou", None), "がっこう");
+        assert_eq!(
+            convert_text("ra-men", Some(ConvertTarget::Katakana)),
+            "ラーメン"
+        );
+        assert_eq!(
+            convert_text("ラーメン", Some(ConvertTarget::Hiragana)),
+            "らーめん"
+        );
+        assert_eq!(
+            convert_text("ひらがな", Some(ConvertTarget::Katakana)),
+            "ヒラガナ"
+        );
+    }
+
+    #[test]
+    fn seeded_study_plan() {
+        let args = study_args(&["--rows", "k,s", "--count", "6", "--seed", "42"]);
+        let config: Config =
+            toml::from_str("study_bold_kana = true\nwriting_system = \"Hiragana\"").unwrap();
+        let plans: Vec<Vec<Kana>> = (0..2)
+            .map(|_| match args.study_page(&config) {
+                Ok(Page::StudyPage(study_page)) => study_page
+                    .queue
+                    .items
+                    .into_iter()
+                    .filter_map(|item| match item {
+                        StudyItem::Kana(kana, _) => Some(kana),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => panic!("no study page"),
+            })
+            .collect();
+        // the first kana of the plan is the current one
+        assert_eq!(plans[0].len(), 5);
+        assert_eq!(plans[0], plans[1]);
+        assert!(plans[0]
+            .iter()
+            .all(|kana| matches!(kana.row(), KanaRow::K | KanaRow::S)));
+        // the options are only for the session
+        assert!(config.kana_selection.contains(&Kana::A));
+    }
+}
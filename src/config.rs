@@ -1,7 +1,11 @@
-use crate::models;
-use std::{path::PathBuf, sync::LazyLock};
+use crate::models::{self, kana::Kana, kana_group::KanaGroup};
+use std::{
+    path::PathBuf,
+    sync::{LazyLock, OnceLock},
+};
 
-static CONFIG_PATH: LazyLock<PathBuf> = LazyLock::new(|| {
+/// Where we store the config by default.
+static CONFIG_FOLDER: LazyLock<PathBuf> = LazyLock::new(|| {
     let config_folder = dirs::config_dir()
         .expect("Config folder for your OS not found")
         .join(env!("CARGO_PKG_NAME"));
@@ -10,39 +14,117 @@ static CONFIG_PATH: LazyLock<PathBuf> = LazyLock::new(|| {
         std::fs::create_dir_all(&config_folder).expect("Could not create config folder");
     }
 
-    config_folder.join("config.toml")
+    config_folder
+});
+
+static CONFIG_PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+static CONFIG_PATH: LazyLock<PathBuf> = LazyLock::new(|| {
+    CONFIG_PATH_OVERRIDE
+        .get()
+        .cloned()
+        .unwrap_or_else(|| CONFIG_FOLDER.join("config.toml"))
+});
+
+/// Where we store everything else we need to save (history...), next to the config file.
+pub(crate) static DATA_FOLDER: LazyLock<PathBuf> = LazyLock::new(|| match CONFIG_PATH.parent() {
+    // a file name alone is in the current folder
+    Some(folder) if folder.as_os_str().is_empty() => PathBuf::from("."),
+    Some(folder) => folder.to_path_buf(),
+    None => CONFIG_FOLDER.clone(),
 });
 
+/// Use another config file, must be called before the config is loaded.
+/// The history, the decks... are read and saved in its folder.
+pub fn override_config_path(config_path: PathBuf) {
+    let _ = CONFIG_PATH_OVERRIDE.set(config_path);
+}
+
 #[derive(Debug)]
 pub enum ConfigError {
     ConfigNotFound,
+    FailedToRead(std::io::Error),
     InvalidConfig(toml::de::Error),
     SerializationError(toml::ser::Error),
     FailedToSave(std::io::Error),
 }
 
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ConfigNotFound => write!(f, "config file not found"),
+            Self::FailedToRead(e) => write!(f, "could not read config: {}", e),
+            Self::InvalidConfig(e) => write!(f, "invalid config: {}", e),
+            Self::SerializationError(e) => write!(f, "could not serialize config: {}", e),
+            Self::FailedToSave(e) => write!(f, "could not save config: {}", e),
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct Config {
     pub study_bold_kana: bool,
+    /// Draw the kana with big blocks, when the terminal is large enough.
+    #[serde(default)]
+    pub study_big_kana: bool,
     pub writing_system: WritingSystem,
+    /// Kanas to study, extended katakana are only studied with katakana.
+    #[serde(default = "default_kana_selection")]
+    pub kana_selection: Vec<Kana>,
+    #[serde(default)]
+    pub study_direction: StudyDirection,
+    #[serde(default, alias = "reverse_answer_mode")]
+    pub answer_mode: AnswerMode,
+    /// Number of choices shown with [`AnswerMode::Choices`].
+    #[serde(default = "default_choices_count")]
+    pub choices_count: usize,
+    /// Study the missed kanas again at the end of a session, until they are known.
+    #[serde(default)]
+    pub retry_missed: bool,
+    /// Spelling of the rōmaji, for the answers and the help.
+    #[serde(default)]
+    pub romanization: Romanization,
+    /// Pause the study while the terminal is not focused, so the timer stops.
+    #[serde(default = "default_pause_on_focus_lost")]
+    pub pause_on_focus_lost: bool,
 }
 
 impl Default for Config {
     fn default() -> Self {
-        Config::parse_from_path(&CONFIG_PATH).unwrap_or_else(|_| {
-            let default_config = Config {
-                study_bold_kana: true,
-                writing_system: WritingSystem::default(),
-            };
-            default_config.save().expect("Could not save config");
-            default_config
-        })
+        Config {
+            study_bold_kana: true,
+            study_big_kana: false,
+            writing_system: WritingSystem::default(),
+            kana_selection: default_kana_selection(),
+            study_direction: StudyDirection::default(),
+            answer_mode: AnswerMode::default(),
+            choices_count: default_choices_count(),
+            retry_missed: false,
+            romanization: Romanization::default(),
+            pause_on_focus_lost: default_pause_on_focus_lost(),
+        }
     }
 }
 
 impl Config {
+    /// Load the config file, it is created with the default config the first time.
+    /// An invalid file is an error, it is never replaced.
+    pub fn load() -> Result<Self, ConfigError> {
+        match Self::parse_from_path(&CONFIG_PATH) {
+            Err(ConfigError::ConfigNotFound) => {
+                let default_config = Self::default();
+                default_config.save()?;
+                Ok(default_config)
+            }
+            result => result,
+        }
+    }
+
     fn parse_from_path(config_path: &PathBuf) -> Result<Self, ConfigError> {
-        let file = std::fs::read_to_string(config_path).map_err(|_| ConfigError::ConfigNotFound)?;
+        let file = std::fs::read_to_string(config_path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => ConfigError::ConfigNotFound,
+            _ => ConfigError::FailedToRead(e),
+        })?;
         toml::from_str(&file).map_err(ConfigError::InvalidConfig)
     }
 
@@ -52,11 +134,58 @@ impl Config {
     }
 }
 
-#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+/// By default, study the basic kanas (gojūon, dakuten and handakuten).
+fn default_kana_selection() -> Vec<Kana> {
+    [KanaGroup::Gojuon, KanaGroup::Dakuten, KanaGroup::Handakuten]
+        .iter()
+        .flat_map(KanaGroup::kanas)
+        .collect()
+}
+
+/// Choices can be picked with the number keys, from 1 to 9.
+pub const CHOICES_COUNT_RANGE: std::ops::RangeInclusive<usize> = 2..=9;
+
+fn default_choices_count() -> usize {
+    4
+}
+
+fn default_pause_on_focus_lost() -> bool {
+    true
+}
+
+#[derive(
+    Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize, clap::ValueEnum,
+)]
 pub enum WritingSystem {
     #[default]
     Hiragana,
     Katakana,
+    /// Each kana is studied in hiragana and in katakana, in the same session.
+    Both,
+}
+
+impl WritingSystem {
+    /// Every script the kanas are shown in.
+    pub fn representations(&self) -> Vec<models::kana::KanaRepresentation> {
+        match self {
+            Self::Hiragana => Vec::from([models::kana::KanaRepresentation::Hiragana]),
+            Self::Katakana => Vec::from([models::kana::KanaRepresentation::Katakana]),
+            Self::Both => Vec::from([
+                models::kana::KanaRepresentation::Hiragana,
+                models::kana::KanaRepresentation::Katakana,
+            ]),
+        }
+    }
+}
+
+impl std::fmt::Display for WritingSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Hiragana => write!(f, "hiragana"),
+            Self::Katakana => write!(f, "katakana"),
+            Self::Both => write!(f, "both"),
+        }
+    }
 }
 
 impl From<models::kana::KanaRepresentation> for WritingSystem {
@@ -68,11 +197,72 @@ impl From<models::kana::KanaRepresentation> for WritingSystem {
     }
 }
 
+/// Outside of a study session, kanas of [`WritingSystem::Both`] are shown in hiragana.
 impl From<WritingSystem> for models::kana::KanaRepresentation {
     fn from(value: WritingSystem) -> Self {
         match value {
-            WritingSystem::Hiragana => Self::Hiragana,
+            WritingSystem::Hiragana | WritingSystem::Both => Self::Hiragana,
             WritingSystem::Katakana => Self::Katakana,
         }
     }
 }
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum StudyDirection {
+    /// Show the kana, answer with its rōmaji.
+    #[default]
+    KanaToRomaji,
+    /// Show the rōmaji, answer with its kana.
+    RomajiToKana,
+}
+
+impl std::fmt::Display for StudyDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::KanaToRomaji => write!(f, "kana\u{2192}rōmaji"),
+            Self::RomajiToKana => write!(f, "rōmaji\u{2192}kana"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum AnswerMode {
+    /// Type the answer, rōmaji is converted to kana while typing when studying from rōmaji to kana.
+    #[default]
+    Typing,
+    /// Pick the answer in a grid of choices, with confusable kanas.
+    Choices,
+}
+
+impl std::fmt::Display for AnswerMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Choices => write!(f, "choices"),
+            Self::Typing => write!(f, "typing"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum Romanization {
+    /// Shi, chi, tsu, fu, ji... the most common one.
+    #[default]
+    Hepburn,
+    /// Si, ti, tu, hu, zi... taught in Japanese schools.
+    Kunrei,
+    /// Like Kunrei-shiki, but ぢ and づ are "di" and "du", and を is "wo".
+    Nihon,
+    /// Any of them is accepted, the help is in Hepburn.
+    Lenient,
+}
+
+impl std::fmt::Display for Romanization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Hepburn => write!(f, "hepburn"),
+            Self::Kunrei => write!(f, "kunrei"),
+            Self::Nihon => write!(f, "nihon"),
+            Self::Lenient => write!(f, "lenient"),
+        }
+    }
+}
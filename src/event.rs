@@ -15,6 +15,10 @@ pub enum Event {
     Mouse(MouseEvent),
     /// Terminal resize.
     Resize(u16, u16),
+    /// The terminal window lost the focus.
+    FocusLost,
+    /// The terminal window got the focus back.
+    FocusGained,
 }
 
 /// Terminal event handler.
@@ -62,8 +66,10 @@ impl EventHandler {
                         _sender.send(Event::Resize(x, y)).unwrap();
                       },
                       CrosstermEvent::FocusLost => {
+                        _sender.send(Event::FocusLost).unwrap();
                       },
                       CrosstermEvent::FocusGained => {
+                        _sender.send(Event::FocusGained).unwrap();
                       },
                       CrosstermEvent::Paste(_) => {
                       },
@@ -87,9 +93,6 @@ impl EventHandler {
         self.receiver
             .recv()
             .await
-            .ok_or(Box::new(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "This is an IO error",
-            )))
+            .ok_or(Box::new(std::io::Error::other("This is an IO error")))
     }
 }
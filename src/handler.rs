@@ -3,7 +3,7 @@ use crate::{
     config::Config,
     AppResult,
 };
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
 
 /// Handles the key events and updates the state of [`App`].
 pub fn handle_key_events(key_event: KeyEvent, app: &mut App, config: &mut Config) -> AppResult<()> {
@@ -18,3 +18,27 @@ pub fn handle_key_events(key_event: KeyEvent, app: &mut App, config: &mut Config
 
     Ok(())
 }
+
+/// Tells the current page that the terminal lost or got back the focus.
+pub fn handle_focus_change(is_focused: bool, app: &mut App, config: &Config) -> AppResult<()> {
+    app.current_page.handle_focus_change(is_focused, config);
+    Ok(())
+}
+
+/// Handles the mouse events and updates the state of [`App`].
+pub fn handle_mouse_events(
+    mouse_event: MouseEvent,
+    app: &mut App,
+    config: &mut Config,
+) -> AppResult<()> {
+    if !app.is_page_shown {
+        return Ok(());
+    }
+    match app.current_page.handle_mouse_events(mouse_event, config) {
+        PageEvent::Nothing => {}
+        PageEvent::Navigate(new_page) => app.current_page = new_page,
+        PageEvent::QuitApp => app.quit(),
+    }
+
+    Ok(())
+}
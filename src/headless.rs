@@ -0,0 +1,214 @@
+//! Line-oriented quiz, for terminals without raw mode and for piped input.
+
+use crate::{
+    config::{Config, StudyDirection},
+    models::{
+        answer::{Answer, AnswerResult, ValidateAnswer},
+        history::SessionRecord,
+        kana::{Kana, KanaRepresentation},
+        StudyPlan,
+    },
+};
+use std::{
+    io::{BufRead, Write},
+    time::Instant,
+};
+
+/// Ask every kana of the plan, one per line, and print the results at the end.
+///
+/// An empty line shows the answer, it counts as wrong. The quiz stops early at the
+/// end of the input, only the kanas that were answered are recorded.
+pub fn run_quiz(
+    mut plan: StudyPlan,
+    config: &Config,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> std::io::Result<SessionRecord> {
+    let start = Instant::now();
+    let total_kanas = plan.len();
+    let mut answers: Vec<Answer> = Vec::with_capacity(total_kanas);
+
+    while let Some((kana, representation)) = plan.pop() {
+        let prompt = match config.study_direction {
+            StudyDirection::KanaToRomaji => kana.to_representation(&representation).to_string(),
+            StudyDirection::RomajiToKana => {
+                format!(
+                    "{} ({})",
+                    kana.romaji_in(&config.romanization),
+                    representation
+                )
+            }
+        };
+        write!(
+            output,
+            "[{}/{}] {} > ",
+            answers.len() + 1,
+            total_kanas,
+            prompt
+        )?;
+        output.flush()?;
+
+        let shown_at = Instant::now();
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            break;
+        }
+        let line = line.trim();
+
+        let is_good = !line.is_empty() && is_answer_valid(&kana, &representation, config, line);
+        let result = if is_good {
+            AnswerResult::Good
+        } else {
+            AnswerResult::Wrong
+        };
+        let mut answer = Answer::new(kana.clone(), result, representation.clone())
+            .with_input(line)
+            .with_response_ms(shown_at.elapsed().as_millis());
+        answer.help_used = line.is_empty();
+        answer.attempts = u32::from(!line.is_empty());
+        answers.push(answer);
+
+        if is_good {
+            writeln!(output, "\u{2714}")?;
+        } else {
+            writeln!(
+                output,
+                "\u{2717} it was {}",
+                expected_answer(&kana, &representation, config)
+            )?;
+        }
+    }
+
+    let record = SessionRecord::new(
+        config.writing_system.clone(),
+        answers,
+        start.elapsed().as_millis(),
+    );
+    write_summary(&record, config, &mut output)?;
+
+    Ok(record)
+}
+
+fn is_answer_valid(
+    kana: &Kana,
+    representation: &KanaRepresentation,
+    config: &Config,
+    input: &str,
+) -> bool {
+    match config.study_direction {
+        StudyDirection::KanaToRomaji => kana.validate_answer_in(input, &config.romanization),
+        StudyDirection::RomajiToKana => {
+            kana.validate_typed_kana(input, representation, &config.romanization)
+        }
+    }
+}
+
+fn expected_answer(kana: &Kana, representation: &KanaRepresentation, config: &Config) -> String {
+    match config.study_direction {
+        StudyDirection::KanaToRomaji => kana.romaji_in(&config.romanization).to_string(),
+        StudyDirection::RomajiToKana => kana.to_representation(representation).to_string(),
+    }
+}
+
+fn write_summary(
+    record: &SessionRecord,
+    config: &Config,
+    output: &mut impl Write,
+) -> std::io::Result<()> {
+    let good_count = record.get_count_by_result(&AnswerResult::Good);
+    let answers_count = record.answers.len();
+    let correct_percent = if answers_count == 0 {
+        0_f64
+    } else {
+        (good_count as f64 / answers_count as f64) * 100_f64
+    };
+    writeln!(output)?;
+    writeln!(
+        output,
+        "{}/{} correct ({:.0}%) in {}s",
+        good_count,
+        answers_count,
+        correct_percent,
+        record.elapsed_ms / 1000
+    )?;
+
+    let missed: Vec<String> = record
+        .answers
+        .iter()
+        .filter(|answer| answer.result == AnswerResult::Wrong)
+        .map(|answer| {
+            format!(
+                "{} {}",
+                answer.kana.to_representation(&answer.representation),
+                answer.kana.romaji_in(&config.romanization)
+            )
+        })
+        .collect();
+    if !missed.is_empty() {
+        writeln!(output, "Missed: {}", missed.join(", "))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(study_direction: StudyDirection) -> Config {
+        let mut config: Config =
+            toml::from_str("study_bold_kana = true\nwriting_system = \"Hiragana\"").unwrap();
+        config.study_direction = study_direction;
+        config
+    }
+
+    fn plan() -> StudyPlan {
+        // kanas are asked from the end of the plan
+        Vec::from([
+            (Kana::Shi, KanaRepresentation::Katakana),
+            (Kana::Ka, KanaRepresentation::Hiragana),
+            (Kana::A, KanaRepresentation::Hiragana),
+        ])
+    }
+
+    #[test]
+    fn quiz_from_piped_input() {
+        let mut output = Vec::new();
+        let record = run_quiz(
+            plan(),
+            &config(StudyDirection::KanaToRomaji),
+            "a\nga\n\n".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.contains("[1/3] あ > \u{2714}"));
+        assert!(output.contains("[2/3] か > \u{2717} it was ka"));
+        assert!(output.contains("[3/3] シ > \u{2717} it was shi"));
+        assert!(output.contains("1/3 correct (33%)"));
+        assert!(output.contains("Missed: か ka, シ shi"));
+        assert_eq!(record.answers.len(), 3);
+        assert_eq!(record.answers[1].input, "ga");
+        assert!(record.answers[2].help_used);
+    }
+
+    #[test]
+    fn quiz_stops_at_end_of_input() {
+        let mut output = Vec::new();
+        let record = run_quiz(
+            plan(),
+            &config(StudyDirection::RomajiToKana),
+            "あ\nka".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.contains("[1/3] a (hiragana) > \u{2714}"));
+        assert!(output.contains("[2/3] ka (hiragana) > \u{2714}"));
+        assert!(output.contains("2/2 correct (100%)"));
+        assert_eq!(record.answers.len(), 2);
+    }
+}
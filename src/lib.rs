@@ -1,7 +1,10 @@
 pub mod app;
+pub mod cli;
 pub mod config;
 pub mod event;
 pub mod handler;
+pub mod headless;
+pub mod transliteration;
 pub mod tui;
 
 mod models;
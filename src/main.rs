@@ -1,8 +1,10 @@
+use clap::Parser;
 use kana_tui::{
     app::App,
-    config::Config,
+    cli::{self, Cli, Command},
+    config::{self, Config},
     event::{Event, EventHandler},
-    handler::handle_key_events,
+    handler::{handle_focus_change, handle_key_events, handle_mouse_events},
     tui::Tui,
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
@@ -10,8 +12,30 @@ use std::io;
 
 #[tokio::main]
 async fn main() -> kana_tui::AppResult<()> {
-    let mut config = Config::default();
-    let mut app = App::new();
+    let cli = Cli::parse();
+    if let Some(config_path) = cli.config {
+        config::override_config_path(config_path);
+    }
+    let mut config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => return exit_on_error(Err(cli::CliError::Config(e))),
+    };
+
+    let result = match cli.command {
+        None => Ok(App::new()),
+        Some(Command::Study(study_args)) => study_args.study_page(&config).map(App::with_page),
+        Some(Command::Quiz(study_args)) => {
+            return exit_on_error(cli::run_quiz(&study_args, &config))
+        }
+        Some(Command::Config { action }) => return exit_on_error(cli::run_config(action, &config)),
+        Some(Command::Stats) => return exit_on_error(cli::print_stats(&config)),
+        Some(Command::Export { path, format }) => return exit_on_error(cli::export(&path, format)),
+        Some(Command::Convert { text, to }) => return exit_on_error(cli::convert(&text, to)),
+    };
+    let mut app = match result {
+        Ok(app) => app,
+        Err(e) => return exit_on_error(Err(e)),
+    };
 
     let backend = CrosstermBackend::new(io::stdout());
     let terminal = Terminal::new(backend)?;
@@ -24,10 +48,21 @@ async fn main() -> kana_tui::AppResult<()> {
         match tui.events.next().await? {
             Event::Tick => app.tick(),
             Event::Key(key_event) => handle_key_events(key_event, &mut app, &mut config)?,
-            _ => {}
+            Event::Mouse(mouse_event) => handle_mouse_events(mouse_event, &mut app, &mut config)?,
+            Event::Resize(width, height) => tui.resize(width, height)?,
+            Event::FocusLost => handle_focus_change(false, &mut app, &config)?,
+            Event::FocusGained => handle_focus_change(true, &mut app, &config)?,
         }
     }
 
     tui.exit()?;
     Ok(())
 }
+
+fn exit_on_error(result: Result<(), cli::CliError>) -> kana_tui::AppResult<()> {
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+    Ok(())
+}
@@ -1,9 +1,273 @@
+use super::kana::{Kana, KanaRepresentation};
+use crate::config::Romanization;
+
 pub trait ValidateAnswer {
     fn validate_answer(&self, answer: &str) -> bool;
+
+    /// Same as [`ValidateAnswer::validate_answer`], with the rōmaji of the romanization system.
+    fn validate_answer_in(&self, answer: &str, _romanization: &Romanization) -> bool {
+        self.validate_answer(answer)
+    }
+}
+
+/// Other accepted spellings of a kana, whatever the romanization system.
+const KANA_ALIASES: &[(Kana, &[&str])] = &[
+    // allow some flexibility
+    (Kana::Chi, &["tchi"]),
+    (Kana::Dji, &["dji", "ji"]),
+    (Kana::Dzu, &["zu"]),
+    // yōon, also accept Kunrei-shiki/wāpuro spellings
+    (Kana::Sha, &["sya"]),
+    (Kana::Shu, &["syu"]),
+    (Kana::Sho, &["syo"]),
+    (Kana::Cha, &["tya", "cya"]),
+    (Kana::Chu, &["cyu"]),
+    (Kana::Cho, &["tyo", "cyo"]),
+    (Kana::Ja, &["zya", "jya"]),
+    (Kana::Ju, &["zyu", "jyu"]),
+    (Kana::Jo, &["zyo", "jyo"]),
+    // extended katakana, people often write the closest native sound
+    (Kana::Va, &["ba"]),
+    (Kana::Vi, &["bi"]),
+    (Kana::Vu, &["bu"]),
+    (Kana::Ve, &["be"]),
+    (Kana::Vo, &["bo"]),
+    (Kana::Ti, &["thi"]),
+    (Kana::Di, &["dhi"]),
+    (Kana::Tyu, &["thu"]),
+    (Kana::Dyu, &["dhu"]),
+    (Kana::Tu, &["twu"]),
+    (Kana::Du, &["dwu"]),
+    (Kana::She, &["sye"]),
+    (Kana::Che, &["tye", "cye"]),
+    (Kana::Je, &["zye", "jye"]),
+];
+
+/// Vowels with a macron (or a circumflex), and the vowel they lengthen.
+pub(crate) const LONG_VOWELS: [(char, char); 10] = [
+    ('ā', 'a'),
+    ('ī', 'i'),
+    ('ū', 'u'),
+    ('ē', 'e'),
+    ('ō', 'o'),
+    ('â', 'a'),
+    ('î', 'i'),
+    ('û', 'u'),
+    ('ê', 'e'),
+    ('ô', 'o'),
+];
+
+/// The other accepted spellings of the kana (e.g. "tchi" for ち).
+pub fn kana_aliases(kana: &Kana) -> &'static [&'static str] {
+    KANA_ALIASES
+        .iter()
+        .find(|(aliased, _)| aliased == kana)
+        .map(|(_, aliases)| *aliases)
+        .unwrap_or_default()
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Normalize a typed rōmaji answer before checking it: the surrounding whitespace is removed,
+/// the case is folded, and the full-width letters of a Japanese input method become ASCII.
+pub fn normalize_answer(answer: &str) -> String {
+    answer
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            c => c,
+        })
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Same as [`normalize_answer`], for the rōmaji of a single kana: a kana is never long,
+/// so a long vowel ("kā", "kâ", "ka-" or "kaー") is read as the short one.
+pub fn normalize_kana_answer(answer: &str) -> String {
+    let answer = normalize_answer(answer);
+    answer
+        .strip_suffix(['-', 'ー'])
+        .unwrap_or(&answer)
+        .chars()
+        .map(|c| {
+            LONG_VOWELS
+                .iter()
+                .find(|(long_vowel, _)| *long_vowel == c)
+                .map_or(c, |(_, vowel)| *vowel)
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub enum AnswerResult {
     Good,
     Wrong,
 }
+
+/// An answer given during a study session.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Answer {
+    pub kana: Kana,
+    pub result: AnswerResult,
+    /// The script the kana was shown in.
+    pub representation: KanaRepresentation,
+    /// What was typed (or picked) for the first answer, empty when help was asked.
+    #[serde(default)]
+    pub input: String,
+    /// How many answers were given until the good one (or until skipping), help excluded.
+    #[serde(default)]
+    pub attempts: u32,
+    /// Whether the answer was shown with help (space).
+    #[serde(default)]
+    pub help_used: bool,
+    /// Time from when the kana was shown to the first answer, pauses excluded.
+    #[serde(default)]
+    pub response_ms: u64,
+}
+
+impl Answer {
+    pub fn new(kana: Kana, result: AnswerResult, representation: KanaRepresentation) -> Self {
+        Self {
+            kana,
+            result,
+            representation,
+            input: String::new(),
+            attempts: 0,
+            help_used: false,
+            response_ms: 0,
+        }
+    }
+
+    pub fn with_input(mut self, input: impl Into<String>) -> Self {
+        self.input = input.into();
+        self
+    }
+
+    pub fn with_response_ms(mut self, response_ms: u128) -> Self {
+        self.response_ms = u64::try_from(response_ms).unwrap_or(u64::MAX);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    const ROMANIZATIONS: [Romanization; 4] = [
+        Romanization::Hepburn,
+        Romanization::Kunrei,
+        Romanization::Nihon,
+        Romanization::Lenient,
+    ];
+
+    /// Ways to type the rōmaji that are not reading mistakes.
+    fn variants(romaji: &str) -> Vec<String> {
+        let mut variants = Vec::from([
+            romaji.to_uppercase(),
+            format!(" {}", romaji),
+            format!("{} ", romaji),
+            format!("\t{}\n", romaji),
+            romaji
+                .chars()
+                .map(|c| char::from_u32(c as u32 + 0xFEE0).unwrap())
+                .collect(),
+        ]);
+        let mut capitalized = romaji.chars();
+        if let Some(first) = capitalized.next() {
+            variants.push(first.to_uppercase().chain(capitalized).collect());
+        }
+        if let Some((last, vowel)) = romaji
+            .char_indices()
+            .last()
+            .and_then(|(i, c)| "aiueo".contains(c).then_some((i, c)))
+        {
+            for (long_vowel, _) in LONG_VOWELS.iter().filter(|(_, v)| *v == vowel) {
+                variants.push(format!("{}{}", &romaji[..last], long_vowel));
+            }
+            variants.push(format!("{}-", romaji));
+            variants.push(format!("{}ー", romaji));
+        }
+        variants
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!(normalize_answer("  Ka\t"), "ka");
+        assert_eq!(normalize_answer("ＳＨＩ"), "shi");
+        assert_eq!(normalize_answer("Kōhī"), "kōhī");
+        assert_eq!(normalize_kana_answer(" Kā "), "ka");
+        assert_eq!(normalize_kana_answer("KÔ"), "ko");
+        assert_eq!(normalize_kana_answer("ka-"), "ka");
+        assert_eq!(normalize_kana_answer("-"), "");
+    }
+
+    #[test]
+    fn every_kana_validates_its_spellings() {
+        for kana in Kana::iter() {
+            for romanization in &ROMANIZATIONS {
+                let romaji = kana.romaji_in(romanization);
+                assert!(
+                    kana.validate_answer_in(romaji, romanization),
+                    "{:?} doesn't validate {:?} in {}",
+                    kana,
+                    romaji,
+                    romanization
+                );
+                for variant in variants(romaji) {
+                    assert!(
+                        kana.validate_answer_in(&variant, romanization),
+                        "{:?} doesn't validate {:?} in {}",
+                        kana,
+                        variant,
+                        romanization
+                    );
+                }
+                assert!(!kana.validate_answer_in("", romanization));
+                assert!(!kana.validate_answer_in(&format!("{}x", romaji), romanization));
+            }
+        }
+    }
+
+    #[test]
+    fn every_kana_validates_its_aliases() {
+        for kana in Kana::iter() {
+            for alias in kana_aliases(&kana) {
+                assert_eq!(normalize_kana_answer(alias), *alias);
+                for romanization in &ROMANIZATIONS {
+                    for variant in variants(alias).iter().chain([&alias.to_string()]) {
+                        assert!(
+                            kana.validate_answer_in(variant, romanization),
+                            "{:?} doesn't validate {:?} in {}",
+                            kana,
+                            variant,
+                            romanization
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn aliases_are_unique() {
+        for (i, (kana, aliases)) in KANA_ALIASES.iter().enumerate() {
+            assert!(!aliases.is_empty(), "{:?} has no alias", kana);
+            assert!(
+                KANA_ALIASES[i + 1..].iter().all(|(other, _)| other != kana),
+                "{:?} is listed twice",
+                kana
+            );
+            for alias in *aliases {
+                assert!(
+                    ROMANIZATIONS
+                        .iter()
+                        .any(|romanization| kana.romaji_in(romanization) != *alias),
+                    "{:?} is always spelled {:?}",
+                    kana,
+                    alias
+                );
+                assert_eq!(aliases.iter().filter(|other| *other == alias).count(), 1);
+            }
+        }
+    }
+}
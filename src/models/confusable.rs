@@ -0,0 +1,214 @@
+//! Kanas that are easily confused with each other, used as distractors for the choices.
+
+use super::{
+    is_written_with,
+    kana::{Kana, KanaRepresentation},
+    kana_group::KanaRow,
+};
+use crate::config::Romanization;
+use rand::seq::SliceRandom;
+use strum::IntoEnumIterator;
+
+/// Hiragana that look alike.
+const SIMILAR_HIRAGANA: &[&[Kana]] = &[
+    &[Kana::A, Kana::O],
+    &[Kana::Nu, Kana::Me, Kana::Ne],
+    &[Kana::Ne, Kana::Re, Kana::Wa],
+    &[Kana::Ru, Kana::Ro, Kana::So],
+    &[Kana::Ha, Kana::Ho, Kana::Ke],
+    &[Kana::Ma, Kana::Mo, Kana::Yo],
+    &[Kana::Sa, Kana::Ki, Kana::Chi],
+    &[Kana::I, Kana::Ri, Kana::Ko],
+    &[Kana::Ko, Kana::Ni, Kana::Ta],
+    &[Kana::Ta, Kana::Na],
+    &[Kana::U, Kana::Ra, Kana::Tsu],
+    &[Kana::Shi, Kana::Tsu],
+    &[Kana::Ku, Kana::He],
+    &[Kana::Ka, Kana::Ya],
+];
+
+/// Katakana that look alike.
+const SIMILAR_KATAKANA: &[&[Kana]] = &[
+    &[Kana::Shi, Kana::Tsu, Kana::Mi],
+    &[Kana::So, Kana::N, Kana::Ri],
+    &[Kana::Shi, Kana::N],
+    &[Kana::Tsu, Kana::So],
+    &[Kana::Ku, Kana::Ke, Kana::Ta],
+    &[Kana::U, Kana::Wa, Kana::Fu, Kana::Ra, Kana::Wo],
+    &[Kana::Su, Kana::Nu, Kana::Ya],
+    &[Kana::A, Kana::Ma],
+    &[Kana::Chi, Kana::Te],
+    &[Kana::Ko, Kana::Yu, Kana::Ro, Kana::E],
+    &[Kana::No, Kana::Me, Kana::Na],
+    &[Kana::Ru, Kana::Re, Kana::Ha],
+    &[Kana::Se, Kana::Hi],
+    &[Kana::Ki, Kana::Sa, Kana::Mo],
+];
+
+/// Kanas that sound alike, or the same sound written with different kanas.
+const SIMILAR_SOUNDS: &[&[Kana]] = &[
+    &[Kana::Shi, Kana::Chi, Kana::Ji, Kana::Dji],
+    &[Kana::Su, Kana::Tsu, Kana::Zu, Kana::Dzu],
+    &[Kana::O, Kana::Wo],
+    &[Kana::N, Kana::Nu, Kana::Mu],
+    &[Kana::Sha, Kana::Cha, Kana::Ja],
+    &[Kana::Shu, Kana::Chu, Kana::Ju],
+    &[Kana::Sho, Kana::Cho, Kana::Jo],
+    &[Kana::Chi, Kana::Ti],
+    &[Kana::Tsu, Kana::Tu],
+    &[Kana::Ji, Kana::Di],
+    &[Kana::Zu, Kana::Du],
+    &[Kana::Ba, Kana::Va],
+    &[Kana::Bi, Kana::Vi],
+    &[Kana::Bu, Kana::Vu],
+    &[Kana::Be, Kana::Ve],
+    &[Kana::Bo, Kana::Vo],
+    &[Kana::E, Kana::Ye],
+    &[Kana::Fu, Kana::Fa, Kana::Fi, Kana::Fe, Kana::Fo],
+];
+
+/// Rows only differing by their (han)dakuten, their kanas at the same position sound alike.
+const SIMILAR_ROWS: &[(KanaRow, KanaRow)] = &[
+    (KanaRow::K, KanaRow::G),
+    (KanaRow::S, KanaRow::Z),
+    (KanaRow::T, KanaRow::D),
+    (KanaRow::H, KanaRow::B),
+    (KanaRow::H, KanaRow::P),
+    (KanaRow::B, KanaRow::P),
+    (KanaRow::Ky, KanaRow::Gy),
+    (KanaRow::Sh, KanaRow::J),
+    (KanaRow::Hy, KanaRow::By),
+    (KanaRow::Hy, KanaRow::Py),
+    (KanaRow::By, KanaRow::Py),
+];
+
+impl Kana {
+    /// Kanas that look like this one in the representation, or that sound like it.
+    pub fn confusables(&self, representation: &KanaRepresentation) -> Vec<Kana> {
+        let similar_looks = match representation {
+            KanaRepresentation::Hiragana => SIMILAR_HIRAGANA,
+            KanaRepresentation::Katakana => SIMILAR_KATAKANA,
+        };
+        let row = self.row();
+        let position = row.kanas().iter().position(|kana| kana.eq(self));
+        let similar_row_kanas = SIMILAR_ROWS.iter().filter_map(|(a, b)| {
+            let other_row = match row {
+                row if row == *a => b,
+                row if row == *b => a,
+                _ => return None,
+            };
+            other_row.kanas().get(position?)
+        });
+
+        let mut confusables: Vec<Kana> = Vec::new();
+        for kana in similar_looks
+            .iter()
+            .chain(SIMILAR_SOUNDS)
+            .filter(|kanas| kanas.contains(self))
+            .flat_map(|kanas| kanas.iter())
+            .chain(similar_row_kanas)
+        {
+            if kana.ne(self) && !confusables.contains(kana) {
+                confusables.push(kana.clone());
+            }
+        }
+
+        confusables
+    }
+}
+
+/// Pick kanas to show next to the kana as choices, confusable kanas first.
+///
+/// When there are not enough confusable kanas, kanas of the same row are picked,
+/// then kanas of the study plan and finally any kana written in the representation.
+/// Two choices never have the same rōmaji in the romanization system of their labels.
+pub fn pick_distractors(
+    kana: &Kana,
+    representation: &KanaRepresentation,
+    romanization: &Romanization,
+    study_plan: &[Kana],
+    count: usize,
+) -> Vec<Kana> {
+    let mut rng = rand::rng();
+    let mut shuffled = |mut kanas: Vec<Kana>| {
+        kanas.shuffle(&mut rng);
+        kanas
+    };
+    let candidates = [
+        shuffled(kana.confusables(representation)),
+        shuffled(kana.row().kanas().to_vec()),
+        shuffled(study_plan.to_vec()),
+        shuffled(Kana::iter().collect()),
+    ];
+
+    let mut distractors: Vec<Kana> = Vec::with_capacity(count);
+    for candidate in candidates
+        .into_iter()
+        .flatten()
+        .filter(|candidate| is_written_with(candidate, representation))
+    {
+        if distractors.len() == count {
+            break;
+        }
+        let romaji = candidate.romaji_in(romanization);
+        if romaji != kana.romaji_in(romanization)
+            && !distractors
+                .iter()
+                .any(|distractor| distractor.romaji_in(romanization) == romaji)
+        {
+            distractors.push(candidate);
+        }
+    }
+
+    distractors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confusable_kanas() {
+        let hiragana = KanaRepresentation::Hiragana;
+        let katakana = KanaRepresentation::Katakana;
+        assert!(Kana::Shi.confusables(&katakana).contains(&Kana::Tsu));
+        assert!(Kana::Shi.confusables(&hiragana).contains(&Kana::Chi));
+        assert!(Kana::Nu.confusables(&hiragana).contains(&Kana::Me));
+        assert!(Kana::Ka.confusables(&hiragana).contains(&Kana::Ga));
+        assert!(Kana::Po.confusables(&hiragana).contains(&Kana::Ho));
+        assert!(Kana::Pyu.confusables(&katakana).contains(&Kana::Byu));
+        for kana in Kana::iter() {
+            assert!(!kana.confusables(&katakana).contains(&kana));
+        }
+    }
+
+    #[test]
+    fn distractors_are_unique() {
+        let study_plan = Vec::from([Kana::A, Kana::I]);
+        let romanizations = [
+            Romanization::Hepburn,
+            Romanization::Kunrei,
+            Romanization::Nihon,
+            Romanization::Lenient,
+        ];
+        for kana in Kana::iter() {
+            for representation in [KanaRepresentation::Hiragana, KanaRepresentation::Katakana] {
+                for romanization in &romanizations {
+                    let distractors =
+                        pick_distractors(&kana, &representation, romanization, &study_plan, 8);
+                    assert_eq!(distractors.len(), 8);
+                    let romaji = kana.romaji_in(romanization);
+                    assert!(distractors.iter().all(|distractor| {
+                        distractor.romaji_in(romanization) != romaji
+                            && is_written_with(distractor, &representation)
+                    }));
+                    for (i, distractor) in distractors.iter().enumerate() {
+                        assert!(distractors[i + 1..].iter().all(|other| {
+                            other.romaji_in(romanization) != distractor.romaji_in(romanization)
+                        }));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+//! Decks of cards written by the user, to study something else than kanas (kanji, words...).
+
+use super::answer::{normalize_answer, AnswerResult, ValidateAnswer};
+use crate::config::DATA_FOLDER;
+use rand::seq::SliceRandom;
+use std::{
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+/// Where the decks shown on the homepage are stored.
+pub static DECKS_FOLDER: LazyLock<PathBuf> = LazyLock::new(|| DATA_FOLDER.join("decks"));
+
+#[derive(Debug)]
+pub enum DeckError {
+    FailedToRead(std::io::Error),
+    InvalidDeck(toml::de::Error),
+    NoCards,
+    NoAnswer(String),
+}
+
+impl std::fmt::Display for DeckError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FailedToRead(e) => write!(f, "could not read deck: {}", e),
+            Self::InvalidDeck(e) => write!(f, "invalid deck file: {}", e),
+            Self::NoCards => write!(f, "the deck has no cards"),
+            Self::NoAnswer(display) => write!(f, "the card {} has no answer", display),
+        }
+    }
+}
+
+/// A deck file, e.g.
+///
+/// ```toml
+/// name = "Numbers"
+///
+/// [[cards]]
+/// display = "一"
+/// answers = ["ichi", "itsu"]
+/// tags = ["kanji"]
+/// notes = "one"
+/// ```
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Deck {
+    /// The name of the file when empty.
+    #[serde(default)]
+    pub name: String,
+    pub cards: Vec<Card>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Card {
+    /// What is shown to the user.
+    pub display: String,
+    /// Every accepted answer, the first one is shown as help.
+    pub answers: Vec<String>,
+    /// Shown next to the card, e.g. what kind of answer is expected.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Shown with the help, e.g. the meaning of a word.
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+impl Card {
+    pub fn first_answer(&self) -> &str {
+        self.answers.first().map(String::as_str).unwrap_or_default()
+    }
+}
+
+/// Answers are not case sensitive.
+impl ValidateAnswer for Card {
+    fn validate_answer(&self, answer: &str) -> bool {
+        let answer = normalize_answer(answer);
+        self.answers
+            .iter()
+            .any(|accepted| normalize_answer(accepted) == answer)
+    }
+}
+
+/// Pick other cards of the deck to show next to the card as choices.
+/// Two choices never have the same first answer.
+pub fn pick_card_distractors(card: &Card, cards: &[Card], count: usize) -> Vec<Card> {
+    let mut candidates = cards.to_vec();
+    candidates.shuffle(&mut rand::rng());
+
+    let mut distractors: Vec<Card> = Vec::with_capacity(count);
+    for candidate in candidates {
+        if distractors.len() == count {
+            break;
+        }
+        let answer = candidate.first_answer();
+        if answer != card.first_answer()
+            && !distractors
+                .iter()
+                .any(|distractor| distractor.first_answer() == answer)
+        {
+            distractors.push(candidate);
+        }
+    }
+
+    distractors
+}
+
+/// An answer given to a card, see [`super::answer::Answer`] for kanas.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct CardAnswer {
+    pub card: Card,
+    pub result: AnswerResult,
+    pub input: String,
+    pub attempts: u32,
+    pub help_used: bool,
+    pub response_ms: u64,
+}
+
+impl Deck {
+    pub fn load(path: &Path) -> Result<Self, DeckError> {
+        let file = std::fs::read_to_string(path).map_err(DeckError::FailedToRead)?;
+        let mut deck = Self::parse(&file)?;
+        if deck.name.is_empty() {
+            deck.name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+        }
+        Ok(deck)
+    }
+
+    fn parse(file: &str) -> Result<Self, DeckError> {
+        let deck: Self = toml::from_str(file).map_err(DeckError::InvalidDeck)?;
+        if deck.cards.is_empty() {
+            return Err(DeckError::NoCards);
+        }
+        if let Some(card) = deck.cards.iter().find(|card| card.answers.is_empty()) {
+            return Err(DeckError::NoAnswer(card.display.clone()));
+        }
+        Ok(deck)
+    }
+
+    /// Every deck of the decks folder, sorted by file name.
+    pub fn load_all() -> Vec<(PathBuf, Result<Self, DeckError>)> {
+        let Ok(entries) = std::fs::read_dir(&*DECKS_FOLDER) else {
+            return Vec::new();
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "toml")
+            })
+            .collect();
+        paths.sort();
+        paths
+            .into_iter()
+            .map(|path| {
+                let deck = Self::load(&path);
+                (path, deck)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_deck() {
+        let deck = Deck::parse(
+            r#"
+            name = "Numbers"
+
+            [[cards]]
+            display = "一"
+            answers = ["ichi", "itsu"]
+            tags = ["kanji"]
+            notes = "one"
+
+            [[cards]]
+            display = "二"
+            answers = ["ni"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(deck.name, "Numbers");
+        assert_eq!(deck.cards.len(), 2);
+        assert_eq!(deck.cards[0].notes.as_deref(), Some("one"));
+        assert!(deck.cards[1].tags.is_empty());
+
+        assert!(deck.cards[0].validate_answer("Itsu "));
+        assert!(!deck.cards[0].validate_answer("ni"));
+
+        assert!(matches!(Deck::parse("cards = []"), Err(DeckError::NoCards)));
+        assert!(matches!(
+            Deck::parse("[[cards]]\ndisplay = \"三\"\nanswers = []"),
+            Err(DeckError::NoAnswer(_))
+        ));
+    }
+}
@@ -0,0 +1,199 @@
+//! Export of the sessions, one row per answer, to load them in spreadsheets.
+
+use super::{answer::AnswerResult, history::SessionRecord};
+use chrono::{DateTime, Local};
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum ExportError {
+    UnknownFormat,
+    SerializationError(serde_json::Error),
+    FailedToSave(std::io::Error),
+}
+
+impl std::fmt::Display for ExportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownFormat => write!(f, "unknown export format, use .json or .csv"),
+            Self::SerializationError(e) => write!(f, "could not serialize results: {}", e),
+            Self::FailedToSave(e) => write!(f, "could not save export: {}", e),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+}
+
+impl ExportFormat {
+    /// The format matching the extension of the path.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// One answer of a session.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ExportRow {
+    pub session_id: String,
+    pub timestamp: DateTime<Local>,
+    pub writing_system: String,
+    pub kana: String,
+    pub romaji: String,
+    pub result: AnswerResult,
+    pub typed: String,
+    pub latency_ms: u64,
+}
+
+impl ExportRow {
+    const CSV_HEADER: &'static str =
+        "session_id,timestamp,writing_system,kana,romaji,result,typed,latency_ms";
+
+    fn to_csv(&self) -> String {
+        let result = match self.result {
+            AnswerResult::Good => "Good",
+            AnswerResult::Wrong => "Wrong",
+        };
+        [
+            self.session_id.clone(),
+            self.timestamp
+                .to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+            self.writing_system.clone(),
+            self.kana.clone(),
+            self.romaji.clone(),
+            result.to_string(),
+            self.typed.clone(),
+            self.latency_ms.to_string(),
+        ]
+        .iter()
+        .map(|field| escape_csv(field))
+        .collect::<Vec<String>>()
+        .join(",")
+    }
+}
+
+/// Quote the fields containing a separator, a quote or a line break.
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+pub fn export_rows(sessions: &[SessionRecord]) -> Vec<ExportRow> {
+    sessions
+        .iter()
+        .flat_map(|session| {
+            session.answers.iter().map(|answer| ExportRow {
+                session_id: session.id(),
+                timestamp: session.timestamp,
+                writing_system: session.writing_system.to_string(),
+                kana: answer
+                    .kana
+                    .to_representation(&answer.representation)
+                    .to_string(),
+                romaji: answer.kana.to_string(),
+                result: answer.result.clone(),
+                typed: answer.input.clone(),
+                latency_ms: answer.response_ms,
+            })
+        })
+        .collect()
+}
+
+pub fn format_export(
+    sessions: &[SessionRecord],
+    format: ExportFormat,
+) -> Result<String, ExportError> {
+    let rows = export_rows(sessions);
+    match format {
+        ExportFormat::Json => {
+            serde_json::to_string_pretty(&rows).map_err(ExportError::SerializationError)
+        }
+        ExportFormat::Csv => Ok(std::iter::once(ExportRow::CSV_HEADER.to_string())
+            .chain(rows.iter().map(ExportRow::to_csv))
+            .map(|line| line + "\n")
+            .collect()),
+    }
+}
+
+/// Write the sessions to the path, in the given format or the one of its extension.
+pub fn export_sessions(
+    sessions: &[SessionRecord],
+    path: &Path,
+    format: Option<ExportFormat>,
+) -> Result<(), ExportError> {
+    let format = format
+        .or_else(|| ExportFormat::from_path(path))
+        .ok_or(ExportError::UnknownFormat)?;
+    let content = format_export(sessions, format)?;
+    std::fs::write(path, content).map_err(ExportError::FailedToSave)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::WritingSystem,
+        models::{
+            answer::Answer,
+            kana::{Kana, KanaRepresentation},
+        },
+    };
+
+    fn sessions() -> Vec<SessionRecord> {
+        Vec::from([SessionRecord::new(
+            WritingSystem::Both,
+            Vec::from([
+                Answer::new(Kana::A, AnswerResult::Good, KanaRepresentation::Hiragana)
+                    .with_input("a")
+                    .with_response_ms(1200),
+                Answer::new(Kana::Shi, AnswerResult::Wrong, KanaRepresentation::Katakana)
+                    .with_input("t\"s,u"),
+            ]),
+            5000,
+        )])
+    }
+
+    #[test]
+    fn export_csv() {
+        let csv = format_export(&sessions(), ExportFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], ExportRow::CSV_HEADER);
+        assert!(lines[1].ends_with(",both,あ,a,Good,a,1200"));
+        assert!(lines[2].ends_with(",both,シ,shi,Wrong,\"t\"\"s,u\",0"));
+    }
+
+    #[test]
+    fn export_json() {
+        let json = format_export(&sessions(), ExportFormat::Json).unwrap();
+        let rows: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["kana"], "あ");
+        assert_eq!(rows[0]["latency_ms"], 1200);
+        assert_eq!(rows[1]["result"], "Wrong");
+        assert_eq!(rows[0]["session_id"], rows[1]["session_id"]);
+    }
+
+    #[test]
+    fn format_from_extension() {
+        assert_eq!(
+            ExportFormat::from_path(Path::new("results.CSV")),
+            Some(ExportFormat::Csv)
+        );
+        assert_eq!(
+            ExportFormat::from_path(Path::new("a/results.json")),
+            Some(ExportFormat::Json)
+        );
+        assert_eq!(ExportFormat::from_path(Path::new("results")), None);
+    }
+}
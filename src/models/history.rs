@@ -0,0 +1,183 @@
+use super::{
+    answer::{Answer, AnswerResult},
+    kana::Kana,
+};
+use crate::config::{WritingSystem, DATA_FOLDER};
+use chrono::{DateTime, Local};
+use std::{path::PathBuf, sync::LazyLock};
+
+static HISTORY_PATH: LazyLock<PathBuf> = LazyLock::new(|| DATA_FOLDER.join("history.toml"));
+
+#[derive(Debug)]
+pub enum HistoryError {
+    InvalidHistory(toml::de::Error),
+    SerializationError(toml::ser::Error),
+    FailedToRead(std::io::Error),
+    FailedToSave(std::io::Error),
+}
+
+impl std::fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidHistory(e) => write!(f, "invalid history file: {}", e),
+            Self::SerializationError(e) => write!(f, "could not serialize history: {}", e),
+            Self::FailedToRead(e) => write!(f, "could not read history: {}", e),
+            Self::FailedToSave(e) => write!(f, "could not save history: {}", e),
+        }
+    }
+}
+
+/// All the finished study sessions, from the oldest to the newest.
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+pub struct History {
+    #[serde(default)]
+    pub sessions: Vec<SessionRecord>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(from = "StoredSessionRecord")]
+pub struct SessionRecord {
+    /// When the session was finished.
+    pub timestamp: DateTime<Local>,
+    pub writing_system: WritingSystem,
+    pub answers: Vec<Answer>,
+    pub elapsed_ms: u64,
+}
+
+/// A [`SessionRecord`] as saved on disk, it may have been saved by an older version.
+#[derive(serde::Deserialize)]
+struct StoredSessionRecord {
+    timestamp: DateTime<Local>,
+    writing_system: WritingSystem,
+    answers: Vec<StoredAnswer>,
+    elapsed_ms: u64,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum StoredAnswer {
+    Answer(Answer),
+    /// Answers were saved without their script, before sessions could mix scripts.
+    KanaAndResult(Kana, AnswerResult),
+}
+
+impl From<StoredSessionRecord> for SessionRecord {
+    fn from(value: StoredSessionRecord) -> Self {
+        let answers = value
+            .answers
+            .into_iter()
+            .map(|answer| match answer {
+                StoredAnswer::Answer(answer) => answer,
+                StoredAnswer::KanaAndResult(kana, result) => {
+                    Answer::new(kana, result, value.writing_system.clone().into())
+                }
+            })
+            .collect();
+        Self {
+            timestamp: value.timestamp,
+            writing_system: value.writing_system,
+            answers,
+            elapsed_ms: value.elapsed_ms,
+        }
+    }
+}
+
+impl SessionRecord {
+    pub fn new(writing_system: WritingSystem, answers: Vec<Answer>, elapsed_ms: u128) -> Self {
+        Self {
+            timestamp: Local::now(),
+            writing_system,
+            answers,
+            elapsed_ms: u64::try_from(elapsed_ms).unwrap_or(u64::MAX),
+        }
+    }
+
+    /// Identifies the session in exports, to the millisecond as quizzes can be short.
+    pub fn id(&self) -> String {
+        self.timestamp.format("%Y%m%d-%H%M%S%.3f").to_string()
+    }
+
+    pub fn get_count_by_result(&self, result: &AnswerResult) -> usize {
+        self.answers
+            .iter()
+            .filter(|answer| answer.result.eq(result))
+            .count()
+    }
+}
+
+impl History {
+    /// Load the history from disk, an history that was never saved is empty.
+    pub fn load() -> Result<Self, HistoryError> {
+        if !HISTORY_PATH.exists() {
+            return Ok(Self::default());
+        }
+        let file = std::fs::read_to_string(&*HISTORY_PATH).map_err(HistoryError::FailedToRead)?;
+        toml::from_str(&file).map_err(HistoryError::InvalidHistory)
+    }
+
+    pub fn save(&self) -> Result<(), HistoryError> {
+        let toml = toml::to_string(&self).map_err(HistoryError::SerializationError)?;
+        std::fs::write(&*HISTORY_PATH, toml).map_err(HistoryError::FailedToSave)
+    }
+
+    /// Add a finished session to the history saved on disk.
+    pub fn append(record: SessionRecord) -> Result<(), HistoryError> {
+        let mut history = Self::load()?;
+        history.sessions.push(record);
+        history.save()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::kana::KanaRepresentation;
+
+    #[test]
+    fn serialize_and_deserialize_history() {
+        let history = History {
+            sessions: Vec::from([SessionRecord::new(
+                WritingSystem::Katakana,
+                Vec::from([
+                    Answer::new(Kana::A, AnswerResult::Good, KanaRepresentation::Katakana),
+                    Answer::new(Kana::Sha, AnswerResult::Wrong, KanaRepresentation::Hiragana),
+                ]),
+                12_345,
+            )]),
+        };
+
+        let toml = toml::to_string(&history).unwrap();
+        let parsed: History = toml::from_str(&toml).unwrap();
+        assert_eq!(parsed.sessions.len(), 1);
+        let session = &parsed.sessions[0];
+        assert_eq!(session.timestamp, history.sessions[0].timestamp);
+        assert_eq!(session.answers, history.sessions[0].answers);
+        assert_eq!(session.elapsed_ms, 12_345);
+        assert_eq!(session.get_count_by_result(&AnswerResult::Wrong), 1);
+
+        // sessions in the same second
+        let mut next_session = session.clone();
+        next_session.timestamp += chrono::Duration::milliseconds(250);
+        assert_ne!(next_session.id(), session.id());
+    }
+
+    #[test]
+    fn deserialize_answers_without_script() {
+        let toml = r#"
+            [[sessions]]
+            timestamp = "2025-01-01T10:00:00+01:00"
+            writing_system = "Katakana"
+            answers = [["A", "Good"], ["Shi", "Wrong"]]
+            elapsed_ms = 1000
+        "#;
+        let parsed: History = toml::from_str(toml).unwrap();
+        assert!(parsed.sessions[0].id().ends_with("0000.000"));
+        assert_eq!(
+            parsed.sessions[0].answers,
+            Vec::from([
+                Answer::new(Kana::A, AnswerResult::Good, KanaRepresentation::Katakana),
+                Answer::new(Kana::Shi, AnswerResult::Wrong, KanaRepresentation::Katakana),
+            ])
+        );
+    }
+}
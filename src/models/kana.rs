@@ -1,6 +1,11 @@
-use strum::EnumIter;
+use super::{
+    answer::{kana_aliases, normalize_kana_answer},
+    kana_group::{KanaGroup, KanaRow},
+};
+use crate::config::Romanization;
+use strum::{EnumIter, IntoEnumIterator};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub enum KanaRepresentation {
     Hiragana,
     Katakana,
@@ -15,7 +20,7 @@ impl std::fmt::Display for KanaRepresentation {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, EnumIter)]
+#[derive(Debug, Clone, PartialEq, EnumIter, serde::Deserialize, serde::Serialize)]
 pub enum Kana {
     N,
     // ∅
@@ -104,9 +109,121 @@ pub enum Kana {
     Pu,
     Pe,
     Po,
+    // --- yōon (contracted sounds)
+    // Ky
+    Kya,
+    Kyu,
+    Kyo,
+    // Sh
+    Sha,
+    Shu,
+    Sho,
+    // Ch
+    Cha,
+    Chu,
+    Cho,
+    // Ny
+    Nya,
+    Nyu,
+    Nyo,
+    // Hy
+    Hya,
+    Hyu,
+    Hyo,
+    // My
+    Mya,
+    Myu,
+    Myo,
+    // Ry
+    Rya,
+    Ryu,
+    Ryo,
+    // Gy
+    Gya,
+    Gyu,
+    Gyo,
+    // J
+    Ja,
+    Ju,
+    Jo,
+    // By
+    Bya,
+    Byu,
+    Byo,
+    // Py
+    Pya,
+    Pyu,
+    Pyo,
+    // --- extended katakana (loanword sounds)
+    // F
+    Fa,
+    Fi,
+    Fe,
+    Fo,
+    Fyu,
+    // T/D
+    Ti,
+    Di,
+    Tu,
+    Du,
+    Tyu,
+    Dyu,
+    // V
+    Va,
+    Vi,
+    Vu,
+    Ve,
+    Vo,
+    // W
+    Wi,
+    We,
+    /// ウォ, not to be confused with [`Kana::Wo`] (ヲ).
+    Uo,
+    // Sh/J/Ch
+    She,
+    Je,
+    Che,
+    // Ts
+    Tsa,
+    Tsi,
+    Tse,
+    Tso,
+    // Y
+    Ye,
+    // Kw/Gw
+    Kwa,
+    Gwa,
 }
 
 impl Kana {
+    pub fn row(&self) -> KanaRow {
+        KanaRow::iter()
+            .find(|row| row.kanas().contains(self))
+            .expect("every kana should belong to a row")
+    }
+
+    pub fn group(&self) -> KanaGroup {
+        self.row().group()
+    }
+
+    /// Yōon are the contracted sounds, written with a small ゃ/ゅ/ょ (e.g. きゃ, しゅ, ちょ).
+    pub fn is_yoon(&self) -> bool {
+        self.group() == KanaGroup::Yoon
+    }
+
+    /// Extended katakana are only used to write loanword sounds (e.g. ファ, ティ, ヴ).
+    /// They should only be studied with [`KanaRepresentation::Katakana`].
+    pub fn is_extended_katakana(&self) -> bool {
+        self.group() == KanaGroup::ExtendedKatakana
+    }
+
+    pub fn to_representation(&self, representation: &KanaRepresentation) -> &str {
+        match representation {
+            KanaRepresentation::Hiragana => self.to_hiragana(),
+            KanaRepresentation::Katakana => self.to_katakana(),
+        }
+    }
+
     pub fn to_hiragana(&self) -> &str {
         match self {
             Self::N => "ん",
@@ -181,6 +298,70 @@ impl Kana {
             Self::Pu => "ぷ",
             Self::Pe => "ぺ",
             Self::Po => "ぽ",
+            // ---
+            Self::Kya => "きゃ",
+            Self::Kyu => "きゅ",
+            Self::Kyo => "きょ",
+            Self::Sha => "しゃ",
+            Self::Shu => "しゅ",
+            Self::Sho => "しょ",
+            Self::Cha => "ちゃ",
+            Self::Chu => "ちゅ",
+            Self::Cho => "ちょ",
+            Self::Nya => "にゃ",
+            Self::Nyu => "にゅ",
+            Self::Nyo => "にょ",
+            Self::Hya => "ひゃ",
+            Self::Hyu => "ひゅ",
+            Self::Hyo => "ひょ",
+            Self::Mya => "みゃ",
+            Self::Myu => "みゅ",
+            Self::Myo => "みょ",
+            Self::Rya => "りゃ",
+            Self::Ryu => "りゅ",
+            Self::Ryo => "りょ",
+            Self::Gya => "ぎゃ",
+            Self::Gyu => "ぎゅ",
+            Self::Gyo => "ぎょ",
+            Self::Ja => "じゃ",
+            Self::Ju => "じゅ",
+            Self::Jo => "じょ",
+            Self::Bya => "びゃ",
+            Self::Byu => "びゅ",
+            Self::Byo => "びょ",
+            Self::Pya => "ぴゃ",
+            Self::Pyu => "ぴゅ",
+            Self::Pyo => "ぴょ",
+            // --- only used by katakana
+            Self::Fa => "ふぁ",
+            Self::Fi => "ふぃ",
+            Self::Fe => "ふぇ",
+            Self::Fo => "ふぉ",
+            Self::Fyu => "ふゅ",
+            Self::Ti => "てぃ",
+            Self::Di => "でぃ",
+            Self::Tu => "とぅ",
+            Self::Du => "どぅ",
+            Self::Tyu => "てゅ",
+            Self::Dyu => "でゅ",
+            Self::Va => "ゔぁ",
+            Self::Vi => "ゔぃ",
+            Self::Vu => "ゔ",
+            Self::Ve => "ゔぇ",
+            Self::Vo => "ゔぉ",
+            Self::Wi => "うぃ",
+            Self::We => "うぇ",
+            Self::Uo => "うぉ",
+            Self::She => "しぇ",
+            Self::Je => "じぇ",
+            Self::Che => "ちぇ",
+            Self::Tsa => "つぁ",
+            Self::Tsi => "つぃ",
+            Self::Tse => "つぇ",
+            Self::Tso => "つぉ",
+            Self::Ye => "いぇ",
+            Self::Kwa => "くぁ",
+            Self::Gwa => "ぐぁ",
         }
     }
 
@@ -258,13 +439,78 @@ impl Kana {
             Self::Pu => "プ",
             Self::Pe => "ペ",
             Self::Po => "ポ",
+            // ---
+            Self::Kya => "キャ",
+            Self::Kyu => "キュ",
+            Self::Kyo => "キョ",
+            Self::Sha => "シャ",
+            Self::Shu => "シュ",
+            Self::Sho => "ショ",
+            Self::Cha => "チャ",
+            Self::Chu => "チュ",
+            Self::Cho => "チョ",
+            Self::Nya => "ニャ",
+            Self::Nyu => "ニュ",
+            Self::Nyo => "ニョ",
+            Self::Hya => "ヒャ",
+            Self::Hyu => "ヒュ",
+            Self::Hyo => "ヒョ",
+            Self::Mya => "ミャ",
+            Self::Myu => "ミュ",
+            Self::Myo => "ミョ",
+            Self::Rya => "リャ",
+            Self::Ryu => "リュ",
+            Self::Ryo => "リョ",
+            Self::Gya => "ギャ",
+            Self::Gyu => "ギュ",
+            Self::Gyo => "ギョ",
+            Self::Ja => "ジャ",
+            Self::Ju => "ジュ",
+            Self::Jo => "ジョ",
+            Self::Bya => "ビャ",
+            Self::Byu => "ビュ",
+            Self::Byo => "ビョ",
+            Self::Pya => "ピャ",
+            Self::Pyu => "ピュ",
+            Self::Pyo => "ピョ",
+            // ---
+            Self::Fa => "ファ",
+            Self::Fi => "フィ",
+            Self::Fe => "フェ",
+            Self::Fo => "フォ",
+            Self::Fyu => "フュ",
+            Self::Ti => "ティ",
+            Self::Di => "ディ",
+            Self::Tu => "トゥ",
+            Self::Du => "ドゥ",
+            Self::Tyu => "テュ",
+            Self::Dyu => "デュ",
+            Self::Va => "ヴァ",
+            Self::Vi => "ヴィ",
+            Self::Vu => "ヴ",
+            Self::Ve => "ヴェ",
+            Self::Vo => "ヴォ",
+            Self::Wi => "ウィ",
+            Self::We => "ウェ",
+            Self::Uo => "ウォ",
+            Self::She => "シェ",
+            Self::Je => "ジェ",
+            Self::Che => "チェ",
+            Self::Tsa => "ツァ",
+            Self::Tsi => "ツィ",
+            Self::Tse => "ツェ",
+            Self::Tso => "ツォ",
+            Self::Ye => "イェ",
+            Self::Kwa => "クァ",
+            Self::Gwa => "グァ",
         }
     }
 }
 
-impl std::fmt::Display for Kana {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let value = match self {
+impl Kana {
+    /// The rōmaji shown for the kana.
+    pub fn romaji(&self) -> &'static str {
+        match self {
             Kana::N => "n",
             Kana::A => "a",
             Kana::I => "i",
@@ -282,7 +528,7 @@ impl std::fmt::Display for Kana {
             Kana::Se => "se",
             Kana::So => "so",
             Kana::Ta => "ta",
-            Kana::Chi => "tchi",
+            Kana::Chi => "chi",
             Kana::Tsu => "tsu",
             Kana::Te => "te",
             Kana::To => "to",
@@ -336,20 +582,137 @@ impl std::fmt::Display for Kana {
             Kana::Pu => "pu",
             Kana::Pe => "pe",
             Kana::Po => "po",
+            Kana::Kya => "kya",
+            Kana::Kyu => "kyu",
+            Kana::Kyo => "kyo",
+            Kana::Sha => "sha",
+            Kana::Shu => "shu",
+            Kana::Sho => "sho",
+            Kana::Cha => "cha",
+            Kana::Chu => "chu",
+            Kana::Cho => "cho",
+            Kana::Nya => "nya",
+            Kana::Nyu => "nyu",
+            Kana::Nyo => "nyo",
+            Kana::Hya => "hya",
+            Kana::Hyu => "hyu",
+            Kana::Hyo => "hyo",
+            Kana::Mya => "mya",
+            Kana::Myu => "myu",
+            Kana::Myo => "myo",
+            Kana::Rya => "rya",
+            Kana::Ryu => "ryu",
+            Kana::Ryo => "ryo",
+            Kana::Gya => "gya",
+            Kana::Gyu => "gyu",
+            Kana::Gyo => "gyo",
+            Kana::Ja => "ja",
+            Kana::Ju => "ju",
+            Kana::Jo => "jo",
+            Kana::Bya => "bya",
+            Kana::Byu => "byu",
+            Kana::Byo => "byo",
+            Kana::Pya => "pya",
+            Kana::Pyu => "pyu",
+            Kana::Pyo => "pyo",
+            Kana::Fa => "fa",
+            Kana::Fi => "fi",
+            Kana::Fe => "fe",
+            Kana::Fo => "fo",
+            Kana::Fyu => "fyu",
+            Kana::Ti => "ti",
+            Kana::Di => "di",
+            Kana::Tu => "tu",
+            Kana::Du => "du",
+            Kana::Tyu => "tyu",
+            Kana::Dyu => "dyu",
+            Kana::Va => "va",
+            Kana::Vi => "vi",
+            Kana::Vu => "vu",
+            Kana::Ve => "ve",
+            Kana::Vo => "vo",
+            Kana::Wi => "wi",
+            Kana::We => "we",
+            Kana::Uo => "wo",
+            Kana::She => "she",
+            Kana::Je => "je",
+            Kana::Che => "che",
+            Kana::Tsa => "tsa",
+            Kana::Tsi => "tsi",
+            Kana::Tse => "tse",
+            Kana::Tso => "tso",
+            Kana::Ye => "ye",
+            Kana::Kwa => "kwa",
+            Kana::Gwa => "gwa",
+        }
+    }
+}
+
+impl Kana {
+    /// The rōmaji of the kana in the romanization system, some kanas share their spelling
+    /// (e.g. じ and ぢ are "zi" in Kunrei-shiki).
+    /// Extended katakana have no Kunrei-shiki or Nihon-shiki spelling, they stay in Hepburn.
+    pub fn romaji_in(&self, romanization: &Romanization) -> &'static str {
+        match (romanization, self) {
+            (Romanization::Hepburn | Romanization::Lenient, kana) => kana.romaji(),
+            (_, Self::Shi) => "si",
+            (_, Self::Chi) => "ti",
+            (_, Self::Tsu) => "tu",
+            (_, Self::Fu) => "hu",
+            (_, Self::Ji) => "zi",
+            (Romanization::Nihon, Self::Dji) => "di",
+            (_, Self::Dji) => "zi",
+            (Romanization::Nihon, Self::Dzu) => "du",
+            (_, Self::Dzu) => "zu",
+            (_, Self::Sha) => "sya",
+            (_, Self::Shu) => "syu",
+            (_, Self::Sho) => "syo",
+            (_, Self::Cha) => "tya",
+            (_, Self::Chu) => "tyu",
+            (_, Self::Cho) => "tyo",
+            (_, Self::Ja) => "zya",
+            (_, Self::Ju) => "zyu",
+            (_, Self::Jo) => "zyo",
+            (Romanization::Kunrei, Self::Wo) => "o",
+            (_, kana) => kana.romaji(),
+        }
+    }
+
+    /// Whether the rōmaji is exactly a spelling of the kana in the romanization system,
+    /// or one of its [aliases](kana_aliases).
+    ///
+    /// When lenient, the spellings of Kunrei-shiki and Nihon-shiki are accepted unless
+    /// they are the Hepburn spelling of another kana (e.g. "ti" is only ティ, not ち).
+    pub(crate) fn is_spelling_in(&self, romaji: &str, romanization: &Romanization) -> bool {
+        let is_system_spelling = match romanization {
+            Romanization::Lenient => {
+                self.romaji() == romaji
+                    || [Romanization::Kunrei, Romanization::Nihon]
+                        .iter()
+                        .any(|romanization| self.romaji_in(romanization) == romaji)
+                        && Self::iter().all(|kana| kana.romaji() != romaji)
+            }
+            romanization => self.romaji_in(romanization) == romaji,
         };
-        write!(f, "{}", value)
+        is_system_spelling || kana_aliases(self).contains(&romaji)
+    }
+}
+
+impl std::fmt::Display for Kana {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.romaji())
     }
 }
 
 impl super::answer::ValidateAnswer for Kana {
     fn validate_answer(&self, answer: &str) -> bool {
-        match (self, answer) {
-            // allow some flexibility
-            (Self::Chi, "chi" | "tchi") => true,
-            (Self::Dji, "dji" | "ji") => true,
-            // same as Display
-            (kana, answer) => kana.to_string() == answer,
-        }
+        self.validate_answer_in(answer, &Romanization::Hepburn)
+    }
+
+    /// The spelling of the romanization system (any of them when lenient), or an alias,
+    /// once the answer is [normalized](normalize_kana_answer).
+    fn validate_answer_in(&self, answer: &str, romanization: &Romanization) -> bool {
+        self.is_spelling_in(&normalize_kana_answer(answer), romanization)
     }
 }
 
@@ -362,6 +725,57 @@ mod tests {
     fn validate_multiple_answers() {
         assert!(Kana::A.validate_answer("a"));
         assert!(Kana::Chi.validate_answer("chi") && Kana::Chi.validate_answer("tchi"));
+        assert_eq!(Kana::Chi.to_string(), "chi");
         assert!(Kana::Dji.validate_answer("dji") && Kana::Dji.validate_answer("ji"));
+        assert!(Kana::Sha.validate_answer("sha") && Kana::Sha.validate_answer("sya"));
+        assert!(Kana::Cho.validate_answer("cho") && Kana::Cho.validate_answer("tyo"));
+        assert!(Kana::Ju.validate_answer("ju") && Kana::Ju.validate_answer("zyu"));
+        assert!(!Kana::Sha.validate_answer("sa"));
+        assert!(Kana::Vu.validate_answer("vu") && Kana::Vu.validate_answer("bu"));
+        assert!(Kana::Ti.validate_answer("ti") && !Kana::Ti.validate_answer("chi"));
+    }
+
+    #[test]
+    fn validate_with_romanization() {
+        let kunrei = Romanization::Kunrei;
+        assert!(Kana::Shi.validate_answer_in("si", &kunrei));
+        assert!(!Kana::Shi.validate_answer_in("shi", &kunrei));
+        assert!(!Kana::Shi.validate_answer("si"));
+        assert!(Kana::Tsu.validate_answer_in("tu", &kunrei));
+        assert!(Kana::Fu.validate_answer_in("hu", &kunrei));
+        assert!(Kana::Dzu.validate_answer_in("zu", &kunrei));
+        assert!(Kana::Dzu.validate_answer_in("du", &Romanization::Nihon));
+        assert!(Kana::Dji.validate_answer_in("di", &Romanization::Nihon));
+        assert!(!Kana::Dji.validate_answer_in("di", &kunrei));
+        assert_eq!(Kana::Wo.romaji_in(&kunrei), "o");
+        assert_eq!(Kana::Wo.romaji_in(&Romanization::Nihon), "wo");
+        // extended katakana stay in Hepburn
+        assert_eq!(Kana::Fa.romaji_in(&kunrei), "fa");
+        assert_eq!(Kana::Ti.romaji_in(&kunrei), "ti");
+
+        let lenient = Romanization::Lenient;
+        assert!(Kana::Shi.validate_answer_in("si", &lenient));
+        assert!(Kana::Shi.validate_answer_in("shi", &lenient));
+        assert_eq!(Kana::Shi.romaji_in(&lenient), "shi");
+        // only the kana spelled this way in Hepburn
+        for romaji in ["ti", "tu", "du", "di", "tyu", "o"] {
+            let kanas: Vec<Kana> = Kana::iter()
+                .filter(|kana| kana.validate_answer_in(romaji, &lenient))
+                .collect();
+            assert_eq!(kanas.len(), 1, "{} is {:?}", romaji, kanas);
+            assert_eq!(kanas[0].romaji(), romaji);
+        }
+    }
+
+    #[test]
+    fn kana_groups_count() {
+        assert_eq!(Kana::iter().filter(Kana::is_yoon).count(), 33);
+        assert_eq!(Kana::iter().filter(Kana::is_extended_katakana).count(), 29);
+        assert_eq!(
+            Kana::iter()
+                .filter(|kana| !kana.is_yoon() && !kana.is_extended_katakana())
+                .count(),
+            71
+        );
     }
 }
@@ -0,0 +1,227 @@
+use super::kana::Kana;
+use strum::{EnumIter, EnumString, IntoEnumIterator};
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum KanaGroup {
+    Gojuon,
+    Dakuten,
+    Handakuten,
+    Yoon,
+    ExtendedKatakana,
+}
+
+impl KanaGroup {
+    pub fn rows(&self) -> Vec<KanaRow> {
+        KanaRow::iter().filter(|row| row.group() == *self).collect()
+    }
+
+    pub fn kanas(&self) -> Vec<Kana> {
+        self.rows()
+            .iter()
+            .flat_map(|row| row.kanas().iter().cloned())
+            .collect()
+    }
+}
+
+impl std::fmt::Display for KanaGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Gojuon => write!(f, "Gojūon"),
+            Self::Dakuten => write!(f, "Dakuten"),
+            Self::Handakuten => write!(f, "Handakuten"),
+            Self::Yoon => write!(f, "Yōon"),
+            Self::ExtendedKatakana => write!(f, "Extended katakana"),
+        }
+    }
+}
+
+/// A row of the kana table, named after its consonant.
+///
+/// Parsed from its name in kebab case (`vowels`, `k`, `ky`, `extended-f`...).
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter, EnumString)]
+#[strum(serialize_all = "kebab-case", ascii_case_insensitive)]
+pub enum KanaRow {
+    // gojūon
+    Vowels,
+    K,
+    S,
+    T,
+    N,
+    H,
+    M,
+    Y,
+    R,
+    W,
+    // dakuten
+    G,
+    Z,
+    D,
+    B,
+    // handakuten
+    P,
+    // yōon
+    Ky,
+    Sh,
+    Ch,
+    Ny,
+    Hy,
+    My,
+    Ry,
+    Gy,
+    J,
+    By,
+    Py,
+    // extended katakana
+    ExtendedF,
+    ExtendedT,
+    ExtendedD,
+    ExtendedV,
+    ExtendedW,
+    ExtendedTs,
+    ExtendedYe,
+    ExtendedKw,
+}
+
+impl KanaRow {
+    pub fn group(&self) -> KanaGroup {
+        match self {
+            Self::Vowels
+            | Self::K
+            | Self::S
+            | Self::T
+            | Self::N
+            | Self::H
+            | Self::M
+            | Self::Y
+            | Self::R
+            | Self::W => KanaGroup::Gojuon,
+            Self::G | Self::Z | Self::D | Self::B => KanaGroup::Dakuten,
+            Self::P => KanaGroup::Handakuten,
+            Self::Ky
+            | Self::Sh
+            | Self::Ch
+            | Self::Ny
+            | Self::Hy
+            | Self::My
+            | Self::Ry
+            | Self::Gy
+            | Self::J
+            | Self::By
+            | Self::Py => KanaGroup::Yoon,
+            Self::ExtendedF
+            | Self::ExtendedT
+            | Self::ExtendedD
+            | Self::ExtendedV
+            | Self::ExtendedW
+            | Self::ExtendedTs
+            | Self::ExtendedYe
+            | Self::ExtendedKw => KanaGroup::ExtendedKatakana,
+        }
+    }
+
+    pub fn kanas(&self) -> &'static [Kana] {
+        match self {
+            Self::Vowels => &[Kana::A, Kana::I, Kana::U, Kana::E, Kana::O],
+            Self::K => &[Kana::Ka, Kana::Ki, Kana::Ku, Kana::Ke, Kana::Ko],
+            Self::S => &[Kana::Sa, Kana::Shi, Kana::Su, Kana::Se, Kana::So],
+            Self::T => &[Kana::Ta, Kana::Chi, Kana::Tsu, Kana::Te, Kana::To],
+            Self::N => &[Kana::Na, Kana::Ni, Kana::Nu, Kana::Ne, Kana::No],
+            Self::H => &[Kana::Ha, Kana::Hi, Kana::Fu, Kana::He, Kana::Ho],
+            Self::M => &[Kana::Ma, Kana::Mi, Kana::Mu, Kana::Me, Kana::Mo],
+            Self::Y => &[Kana::Ya, Kana::Yu, Kana::Yo],
+            Self::R => &[Kana::Ra, Kana::Ri, Kana::Ru, Kana::Re, Kana::Ro],
+            // ん is traditionally written at the end of the table
+            Self::W => &[Kana::Wa, Kana::Wo, Kana::N],
+            Self::G => &[Kana::Ga, Kana::Gi, Kana::Gu, Kana::Ge, Kana::Go],
+            Self::Z => &[Kana::Za, Kana::Ji, Kana::Zu, Kana::Ze, Kana::Zo],
+            Self::D => &[Kana::Da, Kana::Dji, Kana::Dzu, Kana::De, Kana::Do],
+            Self::B => &[Kana::Ba, Kana::Bi, Kana::Bu, Kana::Be, Kana::Bo],
+            Self::P => &[Kana::Pa, Kana::Pi, Kana::Pu, Kana::Pe, Kana::Po],
+            Self::Ky => &[Kana::Kya, Kana::Kyu, Kana::Kyo],
+            Self::Sh => &[Kana::Sha, Kana::Shu, Kana::Sho],
+            Self::Ch => &[Kana::Cha, Kana::Chu, Kana::Cho],
+            Self::Ny => &[Kana::Nya, Kana::Nyu, Kana::Nyo],
+            Self::Hy => &[Kana::Hya, Kana::Hyu, Kana::Hyo],
+            Self::My => &[Kana::Mya, Kana::Myu, Kana::Myo],
+            Self::Ry => &[Kana::Rya, Kana::Ryu, Kana::Ryo],
+            Self::Gy => &[Kana::Gya, Kana::Gyu, Kana::Gyo],
+            Self::J => &[Kana::Ja, Kana::Ju, Kana::Jo],
+            Self::By => &[Kana::Bya, Kana::Byu, Kana::Byo],
+            Self::Py => &[Kana::Pya, Kana::Pyu, Kana::Pyo],
+            Self::ExtendedF => &[Kana::Fa, Kana::Fi, Kana::Fe, Kana::Fo, Kana::Fyu],
+            Self::ExtendedT => &[Kana::Ti, Kana::Tu, Kana::Tyu],
+            Self::ExtendedD => &[Kana::Di, Kana::Du, Kana::Dyu],
+            Self::ExtendedV => &[Kana::Va, Kana::Vi, Kana::Vu, Kana::Ve, Kana::Vo],
+            Self::ExtendedW => &[Kana::Wi, Kana::We, Kana::Uo],
+            Self::ExtendedTs => &[Kana::Tsa, Kana::Tsi, Kana::Tse, Kana::Tso],
+            Self::ExtendedYe => &[Kana::She, Kana::Je, Kana::Che, Kana::Ye],
+            Self::ExtendedKw => &[Kana::Kwa, Kana::Gwa],
+        }
+    }
+}
+
+impl std::fmt::Display for KanaRow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = match self {
+            Self::Vowels => "\u{2205}",
+            Self::K => "K",
+            Self::S => "S",
+            Self::T => "T",
+            Self::N => "N",
+            Self::H => "H",
+            Self::M => "M",
+            Self::Y => "Y",
+            Self::R => "R",
+            Self::W => "W",
+            Self::G => "G",
+            Self::Z => "Z",
+            Self::D => "D",
+            Self::B => "B",
+            Self::P => "P",
+            Self::Ky => "Ky",
+            Self::Sh => "Sh",
+            Self::Ch => "Ch",
+            Self::Ny => "Ny",
+            Self::Hy => "Hy",
+            Self::My => "My",
+            Self::Ry => "Ry",
+            Self::Gy => "Gy",
+            Self::J => "J",
+            Self::By => "By",
+            Self::Py => "Py",
+            Self::ExtendedF => "F",
+            Self::ExtendedT => "T",
+            Self::ExtendedD => "D",
+            Self::ExtendedV => "V",
+            Self::ExtendedW => "W",
+            Self::ExtendedTs => "Ts",
+            Self::ExtendedYe => "Ye",
+            Self::ExtendedKw => "Kw",
+        };
+        f.pad(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_kana_has_exactly_one_row() {
+        for kana in Kana::iter() {
+            let rows_count = KanaRow::iter()
+                .filter(|row| row.kanas().contains(&kana))
+                .count();
+            assert_eq!(rows_count, 1, "{:?} should be in exactly one row", kana);
+        }
+    }
+
+    #[test]
+    fn groups_count() {
+        assert_eq!(KanaGroup::Gojuon.kanas().len(), 46);
+        assert_eq!(KanaGroup::Dakuten.kanas().len(), 20);
+        assert_eq!(KanaGroup::Handakuten.kanas().len(), 5);
+        assert_eq!(KanaGroup::Yoon.kanas().len(), 33);
+        assert_eq!(KanaGroup::ExtendedKatakana.kanas().len(), 29);
+    }
+}
@@ -0,0 +1,139 @@
+//! Type kanas without an IME: rōmaji keystrokes are converted to kana.
+
+use super::kana::{Kana, KanaRepresentation};
+use crate::{config::Romanization, transliteration};
+use strum::IntoEnumIterator;
+
+/// Spellings only used to type kanas, e.g. to tell ウォ apart from ヲ ("wo").
+const INPUT_ALIASES: &[(&str, Kana)] = &[("who", Kana::Uo)];
+
+impl Kana {
+    /// Find the kana written with this rōmaji, alternative spellings are accepted.
+    pub fn from_romaji(romaji: &str) -> Option<Self> {
+        Self::from_romaji_in(romaji, &Romanization::Hepburn)
+    }
+
+    /// Find the kana written with this rōmaji in the romanization system,
+    /// or with the spelling of any other system.
+    ///
+    /// A spelling shared by several kanas is the first one (e.g. "wo" is ヲ, ウォ is "who").
+    pub fn from_romaji_in(romaji: &str, romanization: &Romanization) -> Option<Self> {
+        Self::iter()
+            .find(|kana| kana.romaji_in(romanization) == romaji)
+            .or_else(|| {
+                INPUT_ALIASES
+                    .iter()
+                    .find(|(alias, _)| *alias == romaji)
+                    .map(|(_, kana)| kana.clone())
+            })
+            .or_else(|| {
+                Self::iter().find(|kana| kana.is_spelling_in(romaji, &Romanization::Lenient))
+            })
+    }
+
+    /// Whether the input (rōmaji, or kanas typed with an IME) is the kana of its rōmaji.
+    ///
+    /// The kanas sharing the rōmaji of this one are also right (ヲ and ウォ are both "wo").
+    pub fn validate_typed_kana(
+        &self,
+        input: &str,
+        representation: &KanaRepresentation,
+        romanization: &Romanization,
+    ) -> bool {
+        let romaji = self.romaji_in(romanization);
+        let typed = romaji_to_kana(input, representation, romanization);
+        Self::iter()
+            .filter(|kana| kana.romaji_in(romanization) == romaji)
+            .any(|kana| kana.to_representation(representation) == typed)
+    }
+}
+
+/// Convert the rōmaji to kana, from left to right with the longest known spelling.
+///
+/// The end of the input that can't be converted yet (e.g. the "k" of "kak") is kept as is.
+pub fn romaji_to_kana(
+    romaji: &str,
+    representation: &KanaRepresentation,
+    romanization: &Romanization,
+) -> String {
+    transliteration::to_kana_in(romaji, representation, romanization)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_romaji() {
+        let hiragana = KanaRepresentation::Hiragana;
+        let hepburn = Romanization::Hepburn;
+        assert_eq!(romaji_to_kana("ka", &hiragana, &hepburn), "か");
+        assert_eq!(romaji_to_kana("sushi", &hiragana, &hepburn), "すし");
+        assert_eq!(romaji_to_kana("kyou", &hiragana, &hepburn), "きょう");
+        assert_eq!(romaji_to_kana("k", &hiragana, &hepburn), "k");
+        assert_eq!(romaji_to_kana("kak", &hiragana, &hepburn), "かk");
+        assert_eq!(romaji_to_kana("Sya", &hiragana, &hepburn), "しゃ");
+        let katakana = KanaRepresentation::Katakana;
+        assert_eq!(romaji_to_kana("fa", &katakana, &hepburn), "ファ");
+        assert_eq!(romaji_to_kana("wo", &katakana, &hepburn), "ヲ");
+        assert_eq!(romaji_to_kana("who", &katakana, &hepburn), "ウォ");
+    }
+
+    #[test]
+    fn validate_typed_kana() {
+        let katakana = KanaRepresentation::Katakana;
+        let hepburn = Romanization::Hepburn;
+        assert!(Kana::Ka.validate_typed_kana("ka", &katakana, &hepburn));
+        assert!(Kana::Ka.validate_typed_kana("カ", &katakana, &hepburn));
+        assert!(!Kana::Ka.validate_typed_kana("か", &katakana, &hepburn));
+        assert!(!Kana::Ka.validate_typed_kana("ki", &katakana, &hepburn));
+        // both are "wo"
+        assert!(Kana::Uo.validate_typed_kana("wo", &katakana, &hepburn));
+        assert!(Kana::Uo.validate_typed_kana("who", &katakana, &hepburn));
+        assert!(Kana::Wo.validate_typed_kana("who", &katakana, &hepburn));
+    }
+
+    const ROMANIZATIONS: [Romanization; 4] = [
+        Romanization::Hepburn,
+        Romanization::Kunrei,
+        Romanization::Nihon,
+        Romanization::Lenient,
+    ];
+
+    #[test]
+    fn every_kana_comes_back_from_its_romaji() {
+        for romanization in &ROMANIZATIONS {
+            for kana in Kana::iter() {
+                // or as a kana with the same spelling
+                let romaji = kana.romaji_in(romanization);
+                assert_eq!(
+                    Kana::from_romaji_in(romaji, romanization)
+                        .map(|read| read.romaji_in(romanization)),
+                    Some(romaji),
+                    "{:?} is read as another kana in {}",
+                    kana,
+                    romanization
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn every_kana_can_be_typed() {
+        let representation = KanaRepresentation::Katakana;
+        for romanization in &ROMANIZATIONS {
+            for kana in Kana::iter() {
+                assert!(
+                    kana.validate_typed_kana(
+                        kana.romaji_in(romanization),
+                        &representation,
+                        romanization
+                    ),
+                    "{:?} can't be typed in {}",
+                    kana,
+                    romanization
+                );
+            }
+        }
+    }
+}
@@ -1,18 +1,89 @@
-use kana::Kana;
-use rand::seq::SliceRandom;
+use crate::config::{Config, WritingSystem};
+use chrono::Local;
+use kana::{Kana, KanaRepresentation};
+use rand::{seq::SliceRandom, Rng};
 use strum::IntoEnumIterator;
 
 pub mod answer;
+pub mod confusable;
+pub mod deck;
+pub mod export;
+pub mod history;
 pub mod kana;
+pub mod kana_group;
+pub mod kana_input;
+pub mod session;
+pub mod srs;
+pub mod stats;
+pub mod study_queue;
+pub mod word;
 
-pub fn create_study_plan() -> Vec<Kana> {
-    let mut kanas: Vec<Kana> = Kana::iter().collect();
-    kanas.shuffle(&mut rand::rng());
+/// Kanas to study, each one with the script it is shown in.
+pub type StudyPlan = Vec<(Kana, KanaRepresentation)>;
 
-    kanas
+/// Something studied in a session.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum StudyItem {
+    /// A kana, with the script it is shown in.
+    Kana(Kana, KanaRepresentation),
+    /// A card of a deck written by the user.
+    Card(deck::Card),
+    /// A whole word, answered with the rōmaji of its kanas.
+    Word(word::Word),
+}
+
+impl From<(Kana, KanaRepresentation)> for StudyItem {
+    fn from((kana, representation): (Kana, KanaRepresentation)) -> Self {
+        Self::Kana(kana, representation)
+    }
+}
+
+pub fn create_study_plan(config: &Config) -> StudyPlan {
+    create_study_plan_with_rng(config, &mut rand::rng())
+}
+
+/// Same as [`create_study_plan`], the order only depends on the random number generator.
+pub fn create_study_plan_with_rng(config: &Config, rng: &mut impl Rng) -> StudyPlan {
+    let kanas = Kana::iter().filter(|kana| config.kana_selection.contains(kana));
+    shuffled_plan(kanas, &config.writing_system, rng)
+}
+
+/// Only the kanas that are due in the review schedule, in the script of their card.
+pub fn create_review_plan(schedule: &srs::ReviewSchedule) -> StudyPlan {
+    let mut plan = schedule.due_cards(Local::now());
+    plan.shuffle(&mut rand::rng());
+
+    plan
+}
+
+/// Every kana in every script of the writing system, in a random order.
+fn shuffled_plan(
+    kanas: impl IntoIterator<Item = Kana>,
+    writing_system: &WritingSystem,
+    rng: &mut impl Rng,
+) -> StudyPlan {
+    let mut plan: StudyPlan = kanas
+        .into_iter()
+        .flat_map(|kana| {
+            writing_system
+                .representations()
+                .into_iter()
+                .filter(|representation| is_written_with(&kana, representation))
+                .map(|representation| (kana.clone(), representation))
+                .collect::<StudyPlan>()
+        })
+        .collect();
+    plan.shuffle(rng);
+
+    plan
+}
+
+/// Extended katakana are never studied in hiragana.
+fn is_written_with(kana: &Kana, representation: &KanaRepresentation) -> bool {
+    matches!(representation, KanaRepresentation::Katakana) || !kana.is_extended_katakana()
 }
 
 #[allow(dead_code)]
-pub fn create_small_study_plan() -> Vec<Kana> {
-    Vec::from([Kana::A])
+pub fn create_small_study_plan() -> StudyPlan {
+    Vec::from([(Kana::A, KanaRepresentation::Hiragana)])
 }
@@ -0,0 +1,178 @@
+use super::{
+    answer::Answer, deck::CardAnswer, study_queue::StudyQueue, word::WordAnswer, StudyItem,
+};
+use crate::config::{AnswerMode, Config, Romanization, StudyDirection, WritingSystem, DATA_FOLDER};
+use std::{path::PathBuf, sync::LazyLock};
+
+static SESSION_PATH: LazyLock<PathBuf> = LazyLock::new(|| DATA_FOLDER.join("session.toml"));
+
+#[derive(Debug)]
+pub enum SessionError {
+    InvalidSession(toml::de::Error),
+    SerializationError(toml::ser::Error),
+    FailedToRead(std::io::Error),
+    FailedToSave(std::io::Error),
+    FailedToRemove(std::io::Error),
+}
+
+impl std::fmt::Display for SessionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidSession(e) => write!(f, "invalid session file: {}", e),
+            Self::SerializationError(e) => write!(f, "could not serialize session: {}", e),
+            Self::FailedToRead(e) => write!(f, "could not read session: {}", e),
+            Self::FailedToSave(e) => write!(f, "could not save session: {}", e),
+            Self::FailedToRemove(e) => write!(f, "could not remove session: {}", e),
+        }
+    }
+}
+
+/// A study session left before its end, saved to be resumed later.
+/// Only one session is saved at a time.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct SavedSession {
+    #[serde(flatten)]
+    pub queue: StudyQueue,
+    /// Every item of the study, used to pick the choices.
+    pub choice_pool: Vec<StudyItem>,
+    pub answers: Vec<Answer>,
+    pub card_answers: Vec<CardAnswer>,
+    pub word_answers: Vec<WordAnswer>,
+    /// Time spent studying until the session was left, pauses excluded.
+    pub elapsed_ms: u64,
+    /// Elapsed time when the current item was shown.
+    pub current_shown_ms: u64,
+    /// Settings of the session, the ones of the config when they weren't saved.
+    #[serde(default)]
+    pub settings: Option<SessionSettings>,
+}
+
+/// The settings a session is studied with, from the config when it starts.
+/// They are kept when the session is resumed, even if the config was changed since.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct SessionSettings {
+    pub writing_system: WritingSystem,
+    pub study_direction: StudyDirection,
+    pub answer_mode: AnswerMode,
+    pub romanization: Romanization,
+}
+
+impl From<&Config> for SessionSettings {
+    fn from(config: &Config) -> Self {
+        Self {
+            writing_system: config.writing_system.clone(),
+            study_direction: config.study_direction.clone(),
+            answer_mode: config.answer_mode.clone(),
+            romanization: config.romanization.clone(),
+        }
+    }
+}
+
+impl SessionSettings {
+    /// A copy of the config with the settings of the session.
+    pub fn apply(&self, config: &Config) -> Config {
+        Config {
+            writing_system: self.writing_system.clone(),
+            study_direction: self.study_direction.clone(),
+            answer_mode: self.answer_mode.clone(),
+            romanization: self.romanization.clone(),
+            ..config.clone()
+        }
+    }
+}
+
+impl SavedSession {
+    pub fn exists() -> bool {
+        SESSION_PATH.exists()
+    }
+
+    /// Load the saved session from disk, `None` if there is no session to resume.
+    pub fn load() -> Result<Option<Self>, SessionError> {
+        if !Self::exists() {
+            return Ok(None);
+        }
+        let file = std::fs::read_to_string(&*SESSION_PATH).map_err(SessionError::FailedToRead)?;
+        toml::from_str(&file)
+            .map(Some)
+            .map_err(SessionError::InvalidSession)
+    }
+
+    /// Save the session, replacing the one that was saved before.
+    pub fn save(&self) -> Result<(), SessionError> {
+        let toml = toml::to_string(&self).map_err(SessionError::SerializationError)?;
+        std::fs::write(&*SESSION_PATH, toml).map_err(SessionError::FailedToSave)
+    }
+
+    /// Forget the saved session, once it is resumed.
+    pub fn remove() -> Result<(), SessionError> {
+        if !Self::exists() {
+            return Ok(());
+        }
+        std::fs::remove_file(&*SESSION_PATH).map_err(SessionError::FailedToRemove)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        answer::AnswerResult,
+        deck::Card,
+        kana::{Kana, KanaRepresentation},
+        word::{JlptLevel, Word},
+    };
+
+    #[test]
+    fn serialize_and_deserialize_session() {
+        let word = Word::new("さかな", "fish", JlptLevel::N5).unwrap();
+        let card = Card {
+            display: String::from("犬"),
+            answers: Vec::from([String::from("inu"), String::from("dog")]),
+            tags: Vec::new(),
+            notes: None,
+        };
+        let items = Vec::from([
+            StudyItem::Kana(Kana::A, KanaRepresentation::Hiragana),
+            StudyItem::Card(card.clone()),
+            StudyItem::Word(word.clone()),
+        ]);
+        let session = SavedSession {
+            queue: StudyQueue {
+                items: items.clone(),
+                total_items: 5,
+                current: StudyItem::Kana(Kana::Shi, KanaRepresentation::Katakana),
+                is_retry_round: true,
+                is_current_missed: false,
+                retries: Vec::from([(StudyItem::Kana(Kana::Ka, KanaRepresentation::Hiragana), 2)]),
+            },
+            choice_pool: items,
+            answers: Vec::from([Answer::new(
+                Kana::Ka,
+                AnswerResult::Wrong,
+                KanaRepresentation::Hiragana,
+            )
+            .with_input("ga")]),
+            card_answers: Vec::from([CardAnswer {
+                card,
+                result: AnswerResult::Good,
+                input: String::from("dog"),
+                attempts: 1,
+                help_used: false,
+                response_ms: 1_200,
+            }]),
+            word_answers: Vec::new(),
+            elapsed_ms: 65_432,
+            current_shown_ms: 60_000,
+            settings: Some(SessionSettings {
+                writing_system: WritingSystem::Katakana,
+                study_direction: StudyDirection::RomajiToKana,
+                answer_mode: AnswerMode::Choices,
+                romanization: Romanization::Kunrei,
+            }),
+        };
+
+        let toml = toml::to_string(&session).unwrap();
+        let parsed: SavedSession = toml::from_str(&toml).unwrap();
+        assert_eq!(parsed, session);
+    }
+}
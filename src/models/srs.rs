@@ -0,0 +1,280 @@
+//! Spaced repetition, using the [SM-2](https://super-memory.com/english/ol/sm2.htm) algorithm.
+//!
+//! Each studied kana has a [`Card`] in each script, updated once a session.
+//! Cards are due again after an interval that grows each time the kana is known.
+
+use super::{
+    answer::{Answer, AnswerResult},
+    kana::{Kana, KanaRepresentation},
+    StudyPlan,
+};
+use crate::config::DATA_FOLDER;
+use chrono::{DateTime, Duration, Local};
+use std::{path::PathBuf, sync::LazyLock};
+
+static SCHEDULE_PATH: LazyLock<PathBuf> = LazyLock::new(|| DATA_FOLDER.join("srs.toml"));
+
+const DEFAULT_EASE: f64 = 2.5;
+const MINIMUM_EASE: f64 = 1.3;
+
+#[derive(Debug)]
+pub enum ScheduleError {
+    InvalidSchedule(toml::de::Error),
+    SerializationError(toml::ser::Error),
+    FailedToRead(std::io::Error),
+    FailedToSave(std::io::Error),
+}
+
+impl std::fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidSchedule(e) => write!(f, "invalid review schedule file: {}", e),
+            Self::SerializationError(e) => write!(f, "could not serialize review schedule: {}", e),
+            Self::FailedToRead(e) => write!(f, "could not read review schedule: {}", e),
+            Self::FailedToSave(e) => write!(f, "could not save review schedule: {}", e),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Card {
+    pub kana: Kana,
+    /// Cards saved before scripts had their own card are the hiragana ones.
+    #[serde(default = "hiragana")]
+    pub representation: KanaRepresentation,
+    pub ease: f64,
+    pub interval_days: u32,
+    /// Number of times in a row the kana was known.
+    pub repetitions: u32,
+    pub due: DateTime<Local>,
+}
+
+impl Card {
+    pub fn new(kana: Kana, representation: KanaRepresentation, now: DateTime<Local>) -> Self {
+        Self {
+            kana,
+            representation,
+            ease: DEFAULT_EASE,
+            interval_days: 0,
+            repetitions: 0,
+            due: now,
+        }
+    }
+
+    pub fn is_due(&self, now: DateTime<Local>) -> bool {
+        self.due <= now
+    }
+
+    /// Update the card with the quality of the answer, from 0 (blackout) to 5 (perfect).
+    pub fn review(&mut self, quality: u8, now: DateTime<Local>) {
+        let quality = quality.min(5);
+        if quality >= 3 {
+            self.interval_days = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (f64::from(self.interval_days) * self.ease).round() as u32,
+            };
+            self.repetitions += 1;
+        } else {
+            // start again from the beginning, but keep the ease
+            self.interval_days = 1;
+            self.repetitions = 0;
+        }
+
+        let difficulty = f64::from(5 - quality);
+        self.ease = (self.ease + 0.1 - difficulty * (0.08 + difficulty * 0.02)).max(MINIMUM_EASE);
+        self.due = now + Duration::days(i64::from(self.interval_days));
+    }
+}
+
+fn hiragana() -> KanaRepresentation {
+    KanaRepresentation::Hiragana
+}
+
+/// Quality of an answer used by the SM-2 algorithm.
+pub fn answer_quality(result: &AnswerResult) -> u8 {
+    match result {
+        AnswerResult::Good => 4,
+        AnswerResult::Wrong => 1,
+    }
+}
+
+/// The cards of all the kanas that were studied at least once.
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+pub struct ReviewSchedule {
+    #[serde(default)]
+    pub cards: Vec<Card>,
+}
+
+impl ReviewSchedule {
+    pub fn load() -> Result<Self, ScheduleError> {
+        if !SCHEDULE_PATH.exists() {
+            return Ok(Self::default());
+        }
+        let file = std::fs::read_to_string(&*SCHEDULE_PATH).map_err(ScheduleError::FailedToRead)?;
+        toml::from_str(&file).map_err(ScheduleError::InvalidSchedule)
+    }
+
+    pub fn save(&self) -> Result<(), ScheduleError> {
+        let toml = toml::to_string(&self).map_err(ScheduleError::SerializationError)?;
+        std::fs::write(&*SCHEDULE_PATH, toml).map_err(ScheduleError::FailedToSave)
+    }
+
+    /// Review the cards of the answered kanas, new kanas get a new card.
+    ///
+    /// A card is reviewed once even if it was answered several times in the session,
+    /// a wrong answer wins over the good ones.
+    pub fn update(&mut self, answers: &[Answer], now: DateTime<Local>) {
+        let mut results: Vec<(&Kana, &KanaRepresentation, &AnswerResult)> = Vec::new();
+        for answer in answers {
+            match results.iter_mut().find(|(kana, representation, _)| {
+                answer.kana.eq(kana) && answer.representation.eq(representation)
+            }) {
+                Some((_, _, result)) if answer.result == AnswerResult::Wrong => {
+                    *result = &answer.result
+                }
+                Some(_) => (),
+                None => results.push((&answer.kana, &answer.representation, &answer.result)),
+            }
+        }
+
+        for (kana, representation, result) in results {
+            let card = match self
+                .cards
+                .iter()
+                .position(|card| card.kana.eq(kana) && card.representation.eq(representation))
+            {
+                Some(i) => &mut self.cards[i],
+                None => {
+                    self.cards
+                        .push(Card::new(kana.clone(), representation.clone(), now));
+                    self.cards.last_mut().expect("a card was just pushed")
+                }
+            };
+            card.review(answer_quality(result), now);
+        }
+    }
+
+    /// The kanas to review, in the script of their card.
+    pub fn due_cards(&self, now: DateTime<Local>) -> StudyPlan {
+        self.cards
+            .iter()
+            .filter(|card| card.is_due(now))
+            .map(|card| (card.kana.clone(), card.representation.clone()))
+            .collect()
+    }
+
+    /// Update the schedule saved on disk with the answers of a finished session.
+    pub fn update_saved(answers: &[Answer]) -> Result<(), ScheduleError> {
+        let mut schedule = Self::load()?;
+        schedule.update(answers, Local::now());
+        schedule.save()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intervals_grow_with_good_answers() {
+        let now = Local::now();
+        let mut card = Card::new(Kana::A, KanaRepresentation::Hiragana, now);
+        assert!(card.is_due(now));
+
+        card.review(4, now);
+        assert_eq!(card.interval_days, 1);
+        card.review(4, now);
+        assert_eq!(card.interval_days, 6);
+        card.review(4, now);
+        assert_eq!(card.interval_days, 15);
+        assert_eq!(card.ease, DEFAULT_EASE);
+        assert!(!card.is_due(now));
+        assert!(card.is_due(now + Duration::days(15)));
+    }
+
+    #[test]
+    fn wrong_answer_resets_the_card() {
+        let now = Local::now();
+        let mut card = Card::new(Kana::Shi, KanaRepresentation::Hiragana, now);
+        card.review(4, now);
+        card.review(4, now);
+        card.review(1, now);
+        assert_eq!(card.repetitions, 0);
+        assert_eq!(card.interval_days, 1);
+        assert!(card.ease < DEFAULT_EASE);
+
+        for _ in 0..10 {
+            card.review(0, now);
+        }
+        assert_eq!(card.ease, MINIMUM_EASE);
+    }
+
+    #[test]
+    fn update_schedule() {
+        let now = Local::now();
+        let mut schedule = ReviewSchedule::default();
+        schedule.update(
+            &[
+                Answer::new(Kana::A, AnswerResult::Good, KanaRepresentation::Hiragana),
+                Answer::new(Kana::Tsu, AnswerResult::Wrong, KanaRepresentation::Hiragana),
+            ],
+            now,
+        );
+        assert_eq!(schedule.cards.len(), 2);
+        assert!(schedule.due_cards(now).is_empty());
+        assert_eq!(
+            schedule.due_cards(now + Duration::days(1)),
+            Vec::from([
+                (Kana::A, KanaRepresentation::Hiragana),
+                (Kana::Tsu, KanaRepresentation::Hiragana)
+            ])
+        );
+
+        schedule.update(
+            &[Answer::new(
+                Kana::A,
+                AnswerResult::Good,
+                KanaRepresentation::Hiragana,
+            )],
+            now,
+        );
+        assert_eq!(schedule.cards.len(), 2);
+        assert_eq!(
+            schedule.due_cards(now + Duration::days(1)),
+            Vec::from([(Kana::Tsu, KanaRepresentation::Hiragana)])
+        );
+    }
+
+    #[test]
+    fn one_review_a_session() {
+        let now = Local::now();
+        let mut schedule = ReviewSchedule::default();
+        // あ known twice, ア missed after being known, each script has its own card
+        schedule.update(
+            &[
+                Answer::new(Kana::A, AnswerResult::Good, KanaRepresentation::Hiragana),
+                Answer::new(Kana::A, AnswerResult::Good, KanaRepresentation::Katakana),
+                Answer::new(Kana::A, AnswerResult::Good, KanaRepresentation::Hiragana),
+                Answer::new(Kana::A, AnswerResult::Wrong, KanaRepresentation::Katakana),
+            ],
+            now,
+        );
+        assert_eq!(schedule.cards.len(), 2);
+        assert_eq!(schedule.cards[0].repetitions, 1);
+        assert_eq!(schedule.cards[0].interval_days, 1);
+        assert_eq!(schedule.cards[1].repetitions, 0);
+        assert!(schedule.cards[1].ease < DEFAULT_EASE);
+
+        // a card saved without its script
+        let schedule: ReviewSchedule = toml::from_str(&format!(
+            "[[cards]]\nkana = \"A\"\nease = 2.5\ninterval_days = 1\nrepetitions = 1\ndue = \"{}\"\n",
+            now.to_rfc3339()
+        ))
+        .unwrap();
+        assert_eq!(
+            schedule.cards[0].representation,
+            KanaRepresentation::Hiragana
+        );
+    }
+}
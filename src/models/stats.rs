@@ -0,0 +1,180 @@
+use super::{answer::AnswerResult, history::History, kana::Kana};
+use strum::IntoEnumIterator;
+
+/// Results of all the sessions for one kana.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KanaStats {
+    pub kana: Kana,
+    pub good_count: usize,
+    pub total_count: usize,
+}
+
+impl KanaStats {
+    pub fn wrong_count(&self) -> usize {
+        self.total_count - self.good_count
+    }
+
+    /// Percentage of good answers, between 0 and 100.
+    pub fn accuracy(&self) -> f64 {
+        percent(self.good_count, self.total_count)
+    }
+}
+
+/// Statistics computed from the [`History`].
+#[derive(Debug, Clone, Default)]
+pub struct Stats {
+    pub sessions_count: usize,
+    pub answers_count: usize,
+    pub good_count: usize,
+    pub average_elapsed_ms: u64,
+    /// Only the kanas that were studied at least once, in the kana table order.
+    pub kanas: Vec<KanaStats>,
+    /// Percentage of good answers of each session, from the oldest to the newest.
+    pub accuracy_per_session: Vec<f64>,
+}
+
+impl From<&History> for Stats {
+    fn from(value: &History) -> Self {
+        let sessions_count = value.sessions.len();
+        let answers = value
+            .sessions
+            .iter()
+            .flat_map(|session| session.answers.iter());
+
+        let kanas = Kana::iter()
+            .map(|kana| {
+                let (good_count, total_count) = answers
+                    .clone()
+                    .filter(|answer| answer.kana.eq(&kana))
+                    .fold((0, 0), |(good, total), answer| match answer.result {
+                        AnswerResult::Good => (good + 1, total + 1),
+                        AnswerResult::Wrong => (good, total + 1),
+                    });
+                KanaStats {
+                    kana,
+                    good_count,
+                    total_count,
+                }
+            })
+            .filter(|stats| stats.total_count > 0)
+            .collect();
+
+        let total_elapsed_ms: u64 = value.sessions.iter().map(|s| s.elapsed_ms).sum();
+        Self {
+            sessions_count,
+            answers_count: answers.clone().count(),
+            good_count: answers
+                .filter(|answer| answer.result.eq(&AnswerResult::Good))
+                .count(),
+            average_elapsed_ms: total_elapsed_ms
+                .checked_div(sessions_count as u64)
+                .unwrap_or(0),
+            kanas,
+            accuracy_per_session: value
+                .sessions
+                .iter()
+                .map(|session| {
+                    percent(
+                        session.get_count_by_result(&AnswerResult::Good),
+                        session.answers.len(),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Stats {
+    /// Percentage of good answers of all sessions, between 0 and 100.
+    pub fn accuracy(&self) -> f64 {
+        percent(self.good_count, self.answers_count)
+    }
+
+    /// Kanas with at least one wrong answer, the most missed first.
+    pub fn most_missed(&self, count: usize) -> Vec<&KanaStats> {
+        let mut missed: Vec<&KanaStats> = self
+            .kanas
+            .iter()
+            .filter(|stats| stats.wrong_count() > 0)
+            .collect();
+        missed.sort_by(|a, b| {
+            b.wrong_count()
+                .cmp(&a.wrong_count())
+                .then(a.accuracy().total_cmp(&b.accuracy()))
+        });
+        missed.truncate(count);
+        missed
+    }
+
+    /// Kanas sorted by accuracy, the worst first.
+    pub fn kanas_by_accuracy(&self) -> Vec<&KanaStats> {
+        let mut kanas: Vec<&KanaStats> = self.kanas.iter().collect();
+        kanas.sort_by(|a, b| a.accuracy().total_cmp(&b.accuracy()));
+        kanas
+    }
+}
+
+fn percent(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 0_f64;
+    }
+    (count as f64 / total as f64) * 100_f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::WritingSystem,
+        models::{answer::Answer, history::SessionRecord, kana::KanaRepresentation},
+    };
+
+    fn history() -> History {
+        History {
+            sessions: Vec::from([
+                SessionRecord::new(
+                    WritingSystem::Hiragana,
+                    Vec::from([
+                        Answer::new(Kana::A, AnswerResult::Good, KanaRepresentation::Hiragana),
+                        Answer::new(Kana::Shi, AnswerResult::Wrong, KanaRepresentation::Hiragana),
+                        Answer::new(Kana::Tsu, AnswerResult::Wrong, KanaRepresentation::Hiragana),
+                        Answer::new(Kana::So, AnswerResult::Good, KanaRepresentation::Hiragana),
+                    ]),
+                    10_000,
+                ),
+                SessionRecord::new(
+                    WritingSystem::Katakana,
+                    Vec::from([
+                        Answer::new(Kana::A, AnswerResult::Good, KanaRepresentation::Katakana),
+                        Answer::new(Kana::Shi, AnswerResult::Wrong, KanaRepresentation::Katakana),
+                        Answer::new(Kana::Tsu, AnswerResult::Good, KanaRepresentation::Katakana),
+                        Answer::new(Kana::So, AnswerResult::Good, KanaRepresentation::Katakana),
+                    ]),
+                    20_000,
+                ),
+            ]),
+        }
+    }
+
+    #[test]
+    fn compute_stats() {
+        let stats = Stats::from(&history());
+        assert_eq!(stats.sessions_count, 2);
+        assert_eq!(stats.answers_count, 8);
+        assert_eq!(stats.good_count, 5);
+        assert_eq!(stats.average_elapsed_ms, 15_000);
+        assert_eq!(stats.accuracy_per_session, Vec::from([50_f64, 75_f64]));
+        assert_eq!(stats.kanas.len(), 4);
+
+        let most_missed: Vec<&Kana> = stats.most_missed(5).iter().map(|s| &s.kana).collect();
+        assert_eq!(most_missed, Vec::from([&Kana::Shi, &Kana::Tsu]));
+    }
+
+    #[test]
+    fn empty_history() {
+        let stats = Stats::from(&History::default());
+        assert_eq!(stats.accuracy(), 0_f64);
+        assert_eq!(stats.average_elapsed_ms, 0);
+        assert!(stats.most_missed(5).is_empty());
+    }
+}
@@ -0,0 +1,212 @@
+//! Order of a study session: every item once, then the missed ones until they are known.
+
+use super::{
+    answer::{Answer, AnswerResult},
+    deck::CardAnswer,
+    word::WordAnswer,
+    StudyItem,
+};
+
+/// Items of a study session, the missed ones are retried when
+/// [`Config::retry_missed`](crate::config::Config::retry_missed) is set.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct StudyQueue {
+    /// Items left to study, the next one is at the end.
+    pub items: Vec<StudyItem>,
+    pub total_items: usize,
+    pub current: StudyItem,
+    /// The missed items are studied again, once every item was studied.
+    pub is_retry_round: bool,
+    /// Whether the current item got a wrong answer (or help), it is retried again later.
+    pub is_current_missed: bool,
+    /// How many times each missed item was retried before a good answer.
+    pub retries: Vec<(StudyItem, usize)>,
+}
+
+impl StudyQueue {
+    /// Returns `None` if there is nothing to study.
+    pub fn new(mut items: Vec<StudyItem>) -> Option<Self> {
+        let total_items = items.len();
+        let current = items.pop()?;
+        Some(Self {
+            items,
+            total_items,
+            current,
+            is_retry_round: false,
+            is_current_missed: false,
+            retries: Vec::new(),
+        })
+    }
+
+    /// Go to the next item, `false` when there is none left.
+    ///
+    /// When every item was studied, the `missed` ones are studied again if `retry_missed`.
+    /// An item missed again during this retry round comes back after the others.
+    pub fn next(&mut self, retry_missed: bool, missed: impl FnOnce() -> Vec<StudyItem>) -> bool {
+        if self.is_retry_round && self.is_current_missed {
+            self.items.insert(0, self.current.clone());
+        }
+        if self.items.is_empty() && retry_missed && !self.is_retry_round {
+            self.is_retry_round = true;
+            self.items = missed().into_iter().rev().collect();
+        }
+
+        let Some(next_item) = self.items.pop() else {
+            return false;
+        };
+        if self.is_retry_round {
+            match self
+                .retries
+                .iter_mut()
+                .find(|(item, _)| item.eq(&next_item))
+            {
+                Some((_, count)) => *count += 1,
+                None => self.retries.push((next_item.clone(), 1)),
+            }
+        }
+        self.current = next_item;
+        self.is_current_missed = false;
+        true
+    }
+
+    /// Items left to study, the current one included.
+    pub fn left(&self) -> usize {
+        self.items.len() + 1
+    }
+}
+
+/// Every kana (or card, or word) with a wrong answer, in the order they were answered.
+pub fn missed_items(
+    answers: &[Answer],
+    card_answers: &[CardAnswer],
+    word_answers: &[WordAnswer],
+) -> Vec<StudyItem> {
+    let missed_kanas = answers
+        .iter()
+        .filter(|answer| answer.result == AnswerResult::Wrong)
+        .map(|answer| StudyItem::Kana(answer.kana.clone(), answer.representation.clone()));
+    let missed_cards = card_answers
+        .iter()
+        .filter(|answer| answer.result == AnswerResult::Wrong)
+        .map(|answer| StudyItem::Card(answer.card.clone()));
+    let missed_words = word_answers
+        .iter()
+        .filter(|answer| answer.result == AnswerResult::Wrong)
+        .map(|answer| StudyItem::Word(answer.word.clone()));
+    missed_kanas
+        .chain(missed_cards)
+        .chain(missed_words)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::kana::{Kana, KanaRepresentation};
+
+    fn hiragana(kana: Kana) -> StudyItem {
+        StudyItem::Kana(kana, KanaRepresentation::Hiragana)
+    }
+
+    /// Answer every item until the end of the session, the answers are taken in order
+    /// and the first answer of an item is the one kept.
+    fn study(queue: &mut StudyQueue, results: &[AnswerResult], retry_missed: bool) -> Vec<Answer> {
+        let mut answers: Vec<Answer> = Vec::new();
+        let mut results = results.iter();
+        loop {
+            let result = results.next().expect("an answer for each item");
+            let StudyItem::Kana(kana, representation) = &queue.current else {
+                unreachable!()
+            };
+            if !answers.iter().any(|answer| answer.kana.eq(kana)) {
+                answers.push(Answer::new(
+                    kana.clone(),
+                    result.clone(),
+                    representation.clone(),
+                ));
+            }
+            queue.is_current_missed = *result == AnswerResult::Wrong;
+            if !queue.next(retry_missed, || missed_items(&answers, &[], &[])) {
+                assert!(results.next().is_none(), "some answers are left");
+                return answers;
+            }
+        }
+    }
+
+    #[test]
+    fn missed_items_come_back() {
+        use AnswerResult::{Good, Wrong};
+        let mut queue = StudyQueue::new(Vec::from([
+            hiragana(Kana::U),
+            hiragana(Kana::I),
+            hiragana(Kana::A),
+        ]))
+        .unwrap();
+        assert_eq!(queue.current, hiragana(Kana::A));
+        assert_eq!(queue.left(), 3);
+
+        queue.is_current_missed = true;
+        assert!(queue.next(true, Vec::new));
+        assert_eq!(queue.current, hiragana(Kana::I));
+        assert!(queue.next(true, Vec::new));
+        assert!(queue.next(true, || Vec::from([hiragana(Kana::A)])));
+        assert!(queue.is_retry_round);
+        assert_eq!(queue.current, hiragana(Kana::A));
+        assert!(!queue.next(true, Vec::new));
+
+        // あ and う are missed, あ twice during the retry round
+        let mut queue = StudyQueue::new(Vec::from([
+            hiragana(Kana::U),
+            hiragana(Kana::I),
+            hiragana(Kana::A),
+        ]))
+        .unwrap();
+        let answers = study(
+            &mut queue,
+            &[Wrong, Good, Wrong, Wrong, Good, Wrong, Good],
+            true,
+        );
+        assert_eq!(
+            missed_items(&answers, &[], &[]),
+            Vec::from([hiragana(Kana::A), hiragana(Kana::U)])
+        );
+        assert_eq!(
+            queue.retries,
+            Vec::from([(hiragana(Kana::A), 3), (hiragana(Kana::U), 1)])
+        );
+    }
+
+    #[test]
+    fn retries_end_when_everything_is_known() {
+        use AnswerResult::{Good, Wrong};
+        let items = Vec::from([hiragana(Kana::U), hiragana(Kana::I), hiragana(Kana::A)]);
+
+        let mut queue = StudyQueue::new(items.clone()).unwrap();
+        let answers = study(&mut queue, &[Good, Good, Good], true);
+        assert!(queue.retries.is_empty());
+        assert_eq!(answers.len(), 3);
+
+        // without the retry round, the missed items don't come back
+        let mut queue = StudyQueue::new(items.clone()).unwrap();
+        let answers = study(&mut queue, &[Wrong, Wrong, Good], false);
+        assert!(queue.retries.is_empty());
+        let wrong_count = answers
+            .iter()
+            .filter(|answer| answer.result == Wrong)
+            .count();
+        assert_eq!(wrong_count, 2);
+
+        // the retry round doesn't change the results, only the retries are counted
+        let mut queue = StudyQueue::new(items).unwrap();
+        let answers = study(&mut queue, &[Wrong, Wrong, Good, Good, Wrong, Good], true);
+        let wrong_count = answers
+            .iter()
+            .filter(|answer| answer.result == Wrong)
+            .count();
+        assert_eq!((answers.len(), wrong_count), (3, 2));
+        assert_eq!(
+            queue.retries,
+            Vec::from([(hiragana(Kana::A), 1), (hiragana(Kana::I), 2)])
+        );
+    }
+}
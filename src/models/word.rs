@@ -0,0 +1,534 @@
+//! Words written in kana, to read whole words once the kanas are known.
+
+use super::{
+    answer::{normalize_answer, Answer, AnswerResult, ValidateAnswer},
+    kana::{Kana, KanaRepresentation},
+};
+use crate::{
+    config::{Romanization, WritingSystem},
+    transliteration::{split_kanas, to_kana_in, to_romaji_in},
+};
+use rand::{seq::SliceRandom, Rng};
+use strum::EnumIter;
+
+/// How many words are studied in a session.
+pub const WORDS_PER_SESSION: usize = 20;
+
+/// Level of the Japanese-Language Proficiency Test a word is expected at.
+#[derive(
+    Debug, Clone, Copy, PartialEq, EnumIter, clap::ValueEnum, serde::Deserialize, serde::Serialize,
+)]
+pub enum JlptLevel {
+    N5,
+    N4,
+}
+
+impl std::fmt::Display for JlptLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::N5 => write!(f, "N5"),
+            Self::N4 => write!(f, "N4"),
+        }
+    }
+}
+
+/// Bundled vocabulary, only words usually written in kana and without っ or ー.
+/// The levels follow the common (unofficial) JLPT lists.
+const WORDS: &[(&str, &str, JlptLevel)] = &[
+    // N5, hiragana
+    ("さかな", "fish", JlptLevel::N5),
+    ("いぬ", "dog", JlptLevel::N5),
+    ("ねこ", "cat", JlptLevel::N5),
+    ("とり", "bird", JlptLevel::N5),
+    ("やま", "mountain", JlptLevel::N5),
+    ("かわ", "river", JlptLevel::N5),
+    ("うみ", "sea", JlptLevel::N5),
+    ("そら", "sky", JlptLevel::N5),
+    ("あめ", "rain", JlptLevel::N5),
+    ("ゆき", "snow", JlptLevel::N5),
+    ("はな", "flower", JlptLevel::N5),
+    ("くち", "mouth", JlptLevel::N5),
+    ("みみ", "ear", JlptLevel::N5),
+    ("あたま", "head", JlptLevel::N5),
+    ("かお", "face", JlptLevel::N5),
+    ("からだ", "body", JlptLevel::N5),
+    ("みず", "water", JlptLevel::N5),
+    ("おちゃ", "tea", JlptLevel::N5),
+    ("ごはん", "meal, cooked rice", JlptLevel::N5),
+    ("たまご", "egg", JlptLevel::N5),
+    ("にく", "meat", JlptLevel::N5),
+    ("やさい", "vegetable", JlptLevel::N5),
+    ("くだもの", "fruit", JlptLevel::N5),
+    ("りんご", "apple", JlptLevel::N5),
+    ("あさ", "morning", JlptLevel::N5),
+    ("よる", "night", JlptLevel::N5),
+    ("きょう", "today", JlptLevel::N5),
+    ("あした", "tomorrow", JlptLevel::N5),
+    ("きのう", "yesterday", JlptLevel::N5),
+    ("いま", "now", JlptLevel::N5),
+    ("いえ", "house", JlptLevel::N5),
+    ("へや", "room", JlptLevel::N5),
+    ("まど", "window", JlptLevel::N5),
+    ("つくえ", "desk", JlptLevel::N5),
+    ("いす", "chair", JlptLevel::N5),
+    ("ほん", "book", JlptLevel::N5),
+    ("かばん", "bag", JlptLevel::N5),
+    ("かさ", "umbrella", JlptLevel::N5),
+    ("くつ", "shoes", JlptLevel::N5),
+    ("ふく", "clothes", JlptLevel::N5),
+    ("くるま", "car", JlptLevel::N5),
+    ("でんしゃ", "train", JlptLevel::N5),
+    ("ひこうき", "airplane", JlptLevel::N5),
+    ("えき", "station", JlptLevel::N5),
+    ("みち", "road", JlptLevel::N5),
+    ("まち", "town", JlptLevel::N5),
+    ("やすみ", "holiday", JlptLevel::N5),
+    ("しごと", "work", JlptLevel::N5),
+    ("がくせい", "student", JlptLevel::N5),
+    ("せんせい", "teacher", JlptLevel::N5),
+    ("ともだち", "friend", JlptLevel::N5),
+    ("こども", "child", JlptLevel::N5),
+    ("おとこ", "man", JlptLevel::N5),
+    ("おんな", "woman", JlptLevel::N5),
+    ("なまえ", "name", JlptLevel::N5),
+    ("ことば", "word, language", JlptLevel::N5),
+    ("うた", "song", JlptLevel::N5),
+    ("いろ", "colour", JlptLevel::N5),
+    ("なつ", "summer", JlptLevel::N5),
+    ("ふゆ", "winter", JlptLevel::N5),
+    ("はる", "spring", JlptLevel::N5),
+    ("あき", "autumn", JlptLevel::N5),
+    ("じしょ", "dictionary", JlptLevel::N5),
+    ("としょかん", "library", JlptLevel::N5),
+    ("びょういん", "hospital", JlptLevel::N5),
+    ("ぎんこう", "bank", JlptLevel::N5),
+    ("しんぶん", "newspaper", JlptLevel::N5),
+    ("てがみ", "letter", JlptLevel::N5),
+    ("ちず", "map", JlptLevel::N5),
+    ("しゃしん", "photograph", JlptLevel::N5),
+    ("えいが", "movie", JlptLevel::N5),
+    ("おかね", "money", JlptLevel::N5),
+    ("さいふ", "wallet", JlptLevel::N5),
+    ("とけい", "clock, watch", JlptLevel::N5),
+    ("でんわ", "telephone", JlptLevel::N5),
+    ("たかい", "expensive, high", JlptLevel::N5),
+    ("やすい", "cheap", JlptLevel::N5),
+    ("ふるい", "old", JlptLevel::N5),
+    ("さむい", "cold", JlptLevel::N5),
+    ("おいしい", "delicious", JlptLevel::N5),
+    ("たのしい", "fun", JlptLevel::N5),
+    ("たべる", "to eat", JlptLevel::N5),
+    ("のむ", "to drink", JlptLevel::N5),
+    ("みる", "to see", JlptLevel::N5),
+    ("きく", "to listen, to ask", JlptLevel::N5),
+    ("よむ", "to read", JlptLevel::N5),
+    ("かく", "to write", JlptLevel::N5),
+    ("はなす", "to speak", JlptLevel::N5),
+    ("かう", "to buy", JlptLevel::N5),
+    ("ねる", "to sleep", JlptLevel::N5),
+    ("あるく", "to walk", JlptLevel::N5),
+    ("およぐ", "to swim", JlptLevel::N5),
+    ("すき", "liked", JlptLevel::N5),
+    ("きらい", "disliked", JlptLevel::N5),
+    ("げんき", "healthy, energetic", JlptLevel::N5),
+    ("しずか", "quiet", JlptLevel::N5),
+    ("きれい", "pretty, clean", JlptLevel::N5),
+    ("ゆうめい", "famous", JlptLevel::N5),
+    // N5, katakana
+    ("テレビ", "television", JlptLevel::N5),
+    ("カメラ", "camera", JlptLevel::N5),
+    ("ラジオ", "radio", JlptLevel::N5),
+    ("ピアノ", "piano", JlptLevel::N5),
+    ("パン", "bread", JlptLevel::N5),
+    ("シャツ", "shirt", JlptLevel::N5),
+    ("ホテル", "hotel", JlptLevel::N5),
+    ("ナイフ", "knife", JlptLevel::N5),
+    ("ペン", "pen", JlptLevel::N5),
+    ("ズボン", "trousers", JlptLevel::N5),
+    ("ドア", "door", JlptLevel::N5),
+    ("トイレ", "toilet", JlptLevel::N5),
+    ("ビル", "building", JlptLevel::N5),
+    ("バス", "bus", JlptLevel::N5),
+    ("ハンカチ", "handkerchief", JlptLevel::N5),
+    ("テニス", "tennis", JlptLevel::N5),
+    ("クラス", "class", JlptLevel::N5),
+    ("グラム", "gram", JlptLevel::N5),
+    ("キロ", "kilo", JlptLevel::N5),
+    ("ゼロ", "zero", JlptLevel::N5),
+    ("ワイシャツ", "dress shirt", JlptLevel::N5),
+    ("レストラン", "restaurant", JlptLevel::N5),
+    // N4, hiragana
+    ("こころ", "heart, mind", JlptLevel::N4),
+    ("ゆめ", "dream", JlptLevel::N4),
+    ("かがみ", "mirror", JlptLevel::N4),
+    ("ねだん", "price", JlptLevel::N4),
+    ("ひきだし", "drawer", JlptLevel::N4),
+    ("こうじょう", "factory", JlptLevel::N4),
+    ("ばしょ", "place", JlptLevel::N4),
+    ("じしん", "earthquake", JlptLevel::N4),
+    ("かじ", "fire", JlptLevel::N4),
+    ("さか", "slope", JlptLevel::N4),
+    ("しま", "island", JlptLevel::N4),
+    ("みなと", "harbour", JlptLevel::N4),
+    ("もり", "forest", JlptLevel::N4),
+    ("むら", "village", JlptLevel::N4),
+    ("いなか", "countryside", JlptLevel::N4),
+    ("そふ", "grandfather", JlptLevel::N4),
+    ("そぼ", "grandmother", JlptLevel::N4),
+    ("おや", "parent", JlptLevel::N4),
+    ("むすこ", "son", JlptLevel::N4),
+    ("むすめ", "daughter", JlptLevel::N4),
+    ("つま", "wife", JlptLevel::N4),
+    ("りょかん", "Japanese inn", JlptLevel::N4),
+    ("けしき", "scenery", JlptLevel::N4),
+    ("あじ", "taste", JlptLevel::N4),
+    ("におい", "smell", JlptLevel::N4),
+    ("ほし", "star", JlptLevel::N4),
+    ("つき", "moon", JlptLevel::N4),
+    ("たいふう", "typhoon", JlptLevel::N4),
+    ("すな", "sand", JlptLevel::N4),
+    ("いし", "stone", JlptLevel::N4),
+    ("えだ", "branch", JlptLevel::N4),
+    ("くさ", "grass", JlptLevel::N4),
+    ("こめ", "uncooked rice", JlptLevel::N4),
+    ("おもちゃ", "toy", JlptLevel::N4),
+    ("にんぎょう", "doll", JlptLevel::N4),
+    ("おみやげ", "souvenir", JlptLevel::N4),
+    ("てぶくろ", "gloves", JlptLevel::N4),
+    ("ゆびわ", "ring", JlptLevel::N4),
+    ("いと", "thread", JlptLevel::N4),
+    ("まんが", "comics", JlptLevel::N4),
+    ("かいわ", "conversation", JlptLevel::N4),
+    ("しあい", "match, game", JlptLevel::N4),
+    ("せんそう", "war", JlptLevel::N4),
+    ("けんか", "quarrel", JlptLevel::N4),
+    ("ゆしゅつ", "export", JlptLevel::N4),
+    ("ゆにゅう", "import", JlptLevel::N4),
+    ("ちゅうい", "caution", JlptLevel::N4),
+    ("きそく", "rule", JlptLevel::N4),
+    ("しけん", "exam", JlptLevel::N4),
+    ("じゅぎょう", "class, lesson", JlptLevel::N4),
+    ("しゅくだい", "homework", JlptLevel::N4),
+    ("そつぎょう", "graduation", JlptLevel::N4),
+    ("あんない", "guidance", JlptLevel::N4),
+    ("いけん", "opinion", JlptLevel::N4),
+    ("いのち", "life", JlptLevel::N4),
+    ("ちから", "strength", JlptLevel::N4),
+    // N4, katakana
+    ("パソコン", "personal computer", JlptLevel::N4),
+    ("アルバイト", "part-time job", JlptLevel::N4),
+    ("ガラス", "glass", JlptLevel::N4),
+    ("ガス", "gas", JlptLevel::N4),
+    ("サンダル", "sandals", JlptLevel::N4),
+    ("ジャム", "jam", JlptLevel::N4),
+    ("ステレオ", "stereo", JlptLevel::N4),
+    ("テキスト", "textbook", JlptLevel::N4),
+    ("ソフト", "software", JlptLevel::N4),
+    ("ベル", "bell", JlptLevel::N4),
+    ("レジ", "cash register", JlptLevel::N4),
+    ("ファクス", "fax", JlptLevel::N4),
+    ("パジャマ", "pajamas", JlptLevel::N4),
+    ("ボタン", "button", JlptLevel::N4),
+];
+
+/// A word, made of the kanas it is written with.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Word {
+    pub kanas: Vec<Kana>,
+    /// Words are written in a single script.
+    pub representation: KanaRepresentation,
+    pub meaning: String,
+    pub level: JlptLevel,
+}
+
+impl Word {
+    /// Returns `None` if the text can't be written with the kanas of [`Kana`] in a single script.
+    pub fn new(text: &str, meaning: &str, level: JlptLevel) -> Option<Self> {
+        let kanas = split_kanas(text)
+            .into_iter()
+            .collect::<Option<Vec<Kana>>>()?;
+        [KanaRepresentation::Hiragana, KanaRepresentation::Katakana]
+            .into_iter()
+            .find(|representation| {
+                let written: String = kanas
+                    .iter()
+                    .map(|kana| kana.to_representation(representation))
+                    .collect();
+                written == text
+            })
+            .map(|representation| Self {
+                kanas,
+                representation,
+                meaning: meaning.to_string(),
+                level,
+            })
+    }
+
+    /// Every bundled word of the level.
+    pub fn bundled(level: JlptLevel) -> Vec<Self> {
+        WORDS
+            .iter()
+            .filter(|(_, _, word_level)| *word_level == level)
+            .filter_map(|(text, meaning, level)| Self::new(text, meaning, *level))
+            .collect()
+    }
+
+    /// The expected answer, in Hepburn rōmaji.
+    pub fn romaji(&self) -> String {
+        self.romaji_in(&Romanization::Hepburn)
+    }
+
+    /// The expected answer, in the romanization system.
+    pub fn romaji_in(&self, romanization: &Romanization) -> String {
+        to_romaji_in(&self.to_string(), romanization)
+    }
+
+    /// Positions of the kanas of the word that are missing (or wrong) in the answer.
+    ///
+    /// The answer is split into kanas, which are matched with the kanas of the word in order.
+    /// It is empty when every kana was found, even if the answer has extra characters.
+    pub fn wrong_kanas(&self, answer: &str, romanization: &Romanization) -> Vec<usize> {
+        let typed = self.romaji_kanas(answer, romanization);
+        // longest common subsequence of the kanas of the word and the typed ones
+        let mut lengths = vec![vec![0_usize; typed.len() + 1]; self.kanas.len() + 1];
+        for (i, kana) in self.kanas.iter().enumerate().rev() {
+            for (j, typed_kana) in typed.iter().enumerate().rev() {
+                lengths[i][j] = if typed_kana.as_ref() == Some(kana) {
+                    lengths[i + 1][j + 1] + 1
+                } else {
+                    lengths[i + 1][j].max(lengths[i][j + 1])
+                };
+            }
+        }
+
+        let mut wrong_kanas = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.kanas.len() {
+            if j < typed.len() && typed[j].as_ref() == Some(&self.kanas[i]) {
+                i += 1;
+                j += 1;
+            } else if j < typed.len() && lengths[i][j + 1] >= lengths[i + 1][j] {
+                j += 1;
+            } else {
+                wrong_kanas.push(i);
+                i += 1;
+            }
+        }
+
+        wrong_kanas
+    }
+
+    /// The kanas of the rōmaji, characters that can't be read are `None`.
+    fn romaji_kanas(&self, romaji: &str, romanization: &Romanization) -> Vec<Option<Kana>> {
+        split_kanas(&to_kana_in(
+            &normalize_answer(romaji),
+            &self.representation,
+            romanization,
+        ))
+    }
+}
+
+/// Written in kana.
+impl std::fmt::Display for Word {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text: String = self
+            .kanas
+            .iter()
+            .map(|kana| kana.to_representation(&self.representation))
+            .collect();
+        f.pad(&text)
+    }
+}
+
+/// Each kana can be typed with any of its spellings, the ones of the romanization system come
+/// first (e.g. "ti" is ち in Kunrei-shiki, and ティ in Hepburn). Case is ignored.
+impl ValidateAnswer for Word {
+    fn validate_answer(&self, answer: &str) -> bool {
+        self.validate_answer_in(answer, &Romanization::Hepburn)
+    }
+
+    fn validate_answer_in(&self, answer: &str, romanization: &Romanization) -> bool {
+        self.romaji_kanas(answer, romanization)
+            .into_iter()
+            .eq(self.kanas.iter().cloned().map(Some))
+    }
+}
+
+/// Random words of the level for a session, written in the scripts of the writing system.
+pub fn session_words(
+    level: JlptLevel,
+    writing_system: &WritingSystem,
+    rng: &mut impl Rng,
+) -> Vec<Word> {
+    let representations = writing_system.representations();
+    let mut words: Vec<Word> = Word::bundled(level)
+        .into_iter()
+        .filter(|word| representations.contains(&word.representation))
+        .collect();
+    words.shuffle(rng);
+    words.truncate(WORDS_PER_SESSION);
+    words
+}
+
+/// Pick other words to show next to the word as choices, they never have the same rōmaji.
+pub fn pick_word_distractors(word: &Word, words: &[Word], count: usize) -> Vec<Word> {
+    let mut candidates = words.to_vec();
+    candidates.shuffle(&mut rand::rng());
+
+    let mut distractors: Vec<Word> = Vec::with_capacity(count);
+    for candidate in candidates {
+        if distractors.len() == count {
+            break;
+        }
+        if candidate.kanas != word.kanas
+            && !distractors
+                .iter()
+                .any(|distractor| distractor.kanas == candidate.kanas)
+        {
+            distractors.push(candidate);
+        }
+    }
+
+    distractors
+}
+
+/// An answer given to a word, see [`super::answer::Answer`] for kanas.
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct WordAnswer {
+    pub word: Word,
+    pub result: AnswerResult,
+    pub input: String,
+    pub attempts: u32,
+    pub help_used: bool,
+    pub response_ms: u64,
+    /// Positions of the kanas missed in the first answer, every kana when help was asked.
+    pub wrong_kanas: Vec<usize>,
+}
+
+/// The answers of the kanas read in the words, to save them in the history and the review
+/// schedule. Like in a kana session, a kana has a single answer: it is wrong when it was
+/// missed in any of the words.
+pub fn kana_answers(word_answers: &[WordAnswer]) -> Vec<Answer> {
+    let mut answers: Vec<Answer> = Vec::new();
+    for word_answer in word_answers {
+        let representation = &word_answer.word.representation;
+        for (position, kana) in word_answer.word.kanas.iter().enumerate() {
+            let result = if word_answer.wrong_kanas.contains(&position) {
+                AnswerResult::Wrong
+            } else {
+                AnswerResult::Good
+            };
+            if let Some(answer) = answers
+                .iter_mut()
+                .find(|answer| answer.kana.eq(kana) && answer.representation.eq(representation))
+            {
+                if result == AnswerResult::Wrong {
+                    answer.result = result;
+                }
+                continue;
+            }
+
+            let mut answer = Answer::new(kana.clone(), result, representation.clone())
+                .with_input(word_answer.input.clone());
+            answer.response_ms = word_answer.response_ms;
+            answer.attempts = word_answer.attempts;
+            answer.help_used = word_answer.help_used;
+            answers.push(answer);
+        }
+    }
+    answers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn every_word_is_bundled() {
+        let words: Vec<Word> = JlptLevel::iter().flat_map(Word::bundled).collect();
+        assert_eq!(words.len(), WORDS.len());
+        for word in words {
+            assert!(
+                word.validate_answer(&word.romaji()),
+                "{} can't be answered",
+                word
+            );
+            assert!(word
+                .wrong_kanas(&word.romaji(), &Romanization::Hepburn)
+                .is_empty());
+        }
+    }
+
+    #[test]
+    fn word_from_kanas() {
+        let word = Word::new("でんしゃ", "train", JlptLevel::N5).unwrap();
+        assert_eq!(word.kanas, Vec::from([Kana::De, Kana::N, Kana::Sha]));
+        assert_eq!(word.representation, KanaRepresentation::Hiragana);
+        assert_eq!(word.to_string(), "でんしゃ");
+        assert_eq!(word.romaji(), "densha");
+
+        let word = Word::new("ファクス", "fax", JlptLevel::N4).unwrap();
+        assert_eq!(word.kanas, Vec::from([Kana::Fa, Kana::Ku, Kana::Su]));
+        assert_eq!(word.representation, KanaRepresentation::Katakana);
+
+        // mixed scripts and unknown characters
+        assert!(Word::new("テレび", "", JlptLevel::N5).is_none());
+        assert!(Word::new("きって", "", JlptLevel::N5).is_none());
+    }
+
+    #[test]
+    fn trace_wrong_kanas() {
+        let hepburn = Romanization::Hepburn;
+        let word = Word::new("さかな", "fish", JlptLevel::N5).unwrap();
+        assert!(word.validate_answer(" Sakana"));
+        assert!(!word.validate_answer("sakanaa"));
+        assert_eq!(word.wrong_kanas("sakana", &hepburn), Vec::<usize>::new());
+        assert_eq!(word.wrong_kanas("sagana", &hepburn), Vec::from([1]));
+        assert_eq!(word.wrong_kanas("skana", &hepburn), Vec::from([0]));
+        assert_eq!(word.wrong_kanas("saka", &hepburn), Vec::from([2]));
+        assert_eq!(word.wrong_kanas("", &hepburn), Vec::from([0, 1, 2]));
+
+        let word = Word::new("しゃしん", "photograph", JlptLevel::N5).unwrap();
+        assert!(word.validate_answer("syashin"));
+        assert_eq!(word.wrong_kanas("shashin", &hepburn), Vec::<usize>::new());
+        assert_eq!(word.wrong_kanas("shishin", &hepburn), Vec::from([0]));
+
+        let kunrei = Romanization::Kunrei;
+        let word = Word::new("ちず", "map", JlptLevel::N5).unwrap();
+        assert_eq!(word.romaji_in(&kunrei), "tizu");
+        assert!(word.validate_answer_in("tizu", &kunrei));
+        assert!(!word.validate_answer_in("tizu", &hepburn));
+    }
+
+    #[test]
+    fn answers_of_the_kanas() {
+        let word_answer = |word: &str, input: &str, wrong_kanas: &[usize]| WordAnswer {
+            word: Word::new(word, "", JlptLevel::N5).unwrap(),
+            result: AnswerResult::Wrong,
+            input: String::from(input),
+            attempts: 1,
+            help_used: false,
+            response_ms: 1500,
+            wrong_kanas: wrong_kanas.to_vec(),
+        };
+        let answers = kana_answers(&[word_answer("いぬ", "ine", &[1])]);
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers[0].kana, Kana::I);
+        assert_eq!(answers[0].result, AnswerResult::Good);
+        assert_eq!(answers[1].kana, Kana::Nu);
+        assert_eq!(answers[1].result, AnswerResult::Wrong);
+        assert_eq!(answers[1].response_ms, 1500);
+
+        // each kana is answered once, even when it is read in several words
+        let answers = kana_answers(&[
+            word_answer("はは", "haha", &[]),
+            word_answer("はな", "hama", &[1]),
+            word_answer("なに", "mani", &[0]),
+        ]);
+        let kanas: Vec<Kana> = answers.iter().map(|answer| answer.kana.clone()).collect();
+        assert_eq!(kanas, Vec::from([Kana::Ha, Kana::Na, Kana::Ni]));
+        assert_eq!(answers[0].result, AnswerResult::Good);
+        assert_eq!(answers[1].result, AnswerResult::Wrong);
+    }
+}
@@ -1,11 +1,13 @@
-use super::Homepage;
+use super::{Homepage, SelectionPage};
 use crate::{
-    app::{IPage, PageEvent},
-    config::{Config, WritingSystem},
-    models::kana::KanaRepresentation,
+    app::{self, IPage, PageEvent},
+    config::{
+        AnswerMode, Config, Romanization, StudyDirection, WritingSystem, CHOICES_COUNT_RANGE,
+    },
+    models::kana::Kana,
     widgets::Button,
 };
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
 use ratatui::{
     layout::{Constraint, Flex, Layout, Margin, Rect},
     style::Stylize,
@@ -14,11 +16,23 @@ use ratatui::{
     Frame,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ConfigPage {
     focused_field: ConfigField,
     study_bold_kana: bool,
-    writing_system: WritingSystem,
+    study_big_kana: bool,
+    pub(super) writing_system: WritingSystem,
+    study_direction: StudyDirection,
+    romanization: Romanization,
+    answer_mode: AnswerMode,
+    choices_count: usize,
+    retry_missed: bool,
+    pause_on_focus_lost: bool,
+    /// Updated by the [`SelectionPage`].
+    pub(super) kana_selection: Vec<Kana>,
+    /// Where the fields and the buttons (cancel, save) were rendered, to click on them.
+    field_list_area: Rect,
+    button_areas: [Rect; 2],
 }
 
 impl From<Config> for ConfigPage {
@@ -26,7 +40,17 @@ impl From<Config> for ConfigPage {
         Self {
             focused_field: ConfigField::default(),
             study_bold_kana: value.study_bold_kana,
+            study_big_kana: value.study_big_kana,
             writing_system: value.writing_system,
+            study_direction: value.study_direction,
+            romanization: value.romanization,
+            answer_mode: value.answer_mode,
+            choices_count: value.choices_count,
+            retry_missed: value.retry_missed,
+            pause_on_focus_lost: value.pause_on_focus_lost,
+            kana_selection: value.kana_selection,
+            field_list_area: Rect::default(),
+            button_areas: [Rect::default(); 2],
         }
     }
 }
@@ -46,11 +70,69 @@ impl IPage for ConfigPage {
 
         let width = usize::from(middle_area.width);
         let field_list = List::new(Vec::from([
-            self.bold_kana_field(width, self.focused_field == ConfigField::BoldKana),
-            Line::from(""),
-            self.writing_system_field(width, self.focused_field == ConfigField::WritingSystemField),
+            checkbox_field(
+                "Kana in bold",
+                self.study_bold_kana,
+                width,
+                self.focused_field == ConfigField::BoldKana,
+            ),
+            checkbox_field(
+                "Big kana",
+                self.study_big_kana,
+                width,
+                self.focused_field == ConfigField::BigKana,
+            ),
+            input_field(
+                "Writing system",
+                format!("\u{2BC7} {} \u{2BC8}", self.writing_system),
+                width,
+                self.focused_field == ConfigField::WritingSystemField,
+            ),
+            input_field(
+                "Direction",
+                format!("\u{2BC7} {} \u{2BC8}", self.study_direction),
+                width,
+                self.focused_field == ConfigField::StudyDirectionField,
+            ),
+            input_field(
+                "Rōmaji",
+                format!("\u{2BC7} {} \u{2BC8}", self.romanization),
+                width,
+                self.focused_field == ConfigField::RomanizationField,
+            ),
+            input_field(
+                "Answer with",
+                format!("\u{2BC7} {} \u{2BC8}", self.answer_mode),
+                width,
+                self.focused_field == ConfigField::AnswerModeField,
+            ),
+            input_field(
+                "Choices",
+                format!("\u{2BC7} {} \u{2BC8}", self.choices_count),
+                width,
+                self.focused_field == ConfigField::ChoicesCount,
+            ),
+            checkbox_field(
+                "Retry missed kanas",
+                self.retry_missed,
+                width,
+                self.focused_field == ConfigField::RetryMissed,
+            ),
+            checkbox_field(
+                "Pause when unfocused",
+                self.pause_on_focus_lost,
+                width,
+                self.focused_field == ConfigField::PauseOnFocusLost,
+            ),
+            input_field(
+                "Kanas",
+                format!("{} selected \u{2BC8}", self.kana_selection.len()),
+                width,
+                self.focused_field == ConfigField::KanaSelection,
+            ),
         ]));
         frame.render_widget(field_list, middle_area);
+        self.field_list_area = middle_area;
 
         frame.render_widget(
             Paragraph::new(Vec::from([
@@ -73,19 +155,66 @@ impl IPage for ConfigPage {
             (ConfigField::BoldKana, KeyCode::Enter | KeyCode::Char(' ')) => {
                 self.study_bold_kana = !self.study_bold_kana;
             }
+            (ConfigField::BigKana, KeyCode::Enter | KeyCode::Char(' ')) => {
+                self.study_big_kana = !self.study_big_kana;
+            }
             (ConfigField::WritingSystemField, KeyCode::Enter | KeyCode::Char(' ')) => {
                 self.writing_system = match self.writing_system {
                     WritingSystem::Hiragana => WritingSystem::Katakana,
-                    WritingSystem::Katakana => WritingSystem::Hiragana,
+                    WritingSystem::Katakana => WritingSystem::Both,
+                    WritingSystem::Both => WritingSystem::Hiragana,
                 };
             }
+            (ConfigField::StudyDirectionField, KeyCode::Enter | KeyCode::Char(' ')) => {
+                self.study_direction = match self.study_direction {
+                    StudyDirection::KanaToRomaji => StudyDirection::RomajiToKana,
+                    StudyDirection::RomajiToKana => StudyDirection::KanaToRomaji,
+                };
+            }
+            (ConfigField::RomanizationField, KeyCode::Enter | KeyCode::Char(' ')) => {
+                self.romanization = match self.romanization {
+                    Romanization::Hepburn => Romanization::Kunrei,
+                    Romanization::Kunrei => Romanization::Nihon,
+                    Romanization::Nihon => Romanization::Lenient,
+                    Romanization::Lenient => Romanization::Hepburn,
+                };
+            }
+            (ConfigField::AnswerModeField, KeyCode::Enter | KeyCode::Char(' ')) => {
+                self.answer_mode = match self.answer_mode {
+                    AnswerMode::Choices => AnswerMode::Typing,
+                    AnswerMode::Typing => AnswerMode::Choices,
+                };
+            }
+            (ConfigField::ChoicesCount, KeyCode::Enter | KeyCode::Char(' ')) => {
+                self.choices_count = match self.choices_count + 1 {
+                    count if CHOICES_COUNT_RANGE.contains(&count) => count,
+                    _ => *CHOICES_COUNT_RANGE.start(),
+                };
+            }
+            (ConfigField::RetryMissed, KeyCode::Enter | KeyCode::Char(' ')) => {
+                self.retry_missed = !self.retry_missed;
+            }
+            (ConfigField::PauseOnFocusLost, KeyCode::Enter | KeyCode::Char(' ')) => {
+                self.pause_on_focus_lost = !self.pause_on_focus_lost;
+            }
+            (ConfigField::KanaSelection, KeyCode::Enter | KeyCode::Char(' ')) => {
+                return PageEvent::Navigate(SelectionPage::from(self.clone()).into());
+            }
             (ConfigField::Action(BottomAction::Cancel), KeyCode::Enter | KeyCode::Char(' ')) => {
                 return PageEvent::Navigate(Homepage::default().into());
             }
             (ConfigField::Action(BottomAction::Save), KeyCode::Enter | KeyCode::Char(' ')) => {
                 // updating fields (better way to do it?)
                 config.study_bold_kana = self.study_bold_kana;
+                config.study_big_kana = self.study_big_kana;
                 config.writing_system = self.writing_system.clone();
+                config.study_direction = self.study_direction.clone();
+                config.romanization = self.romanization.clone();
+                config.answer_mode = self.answer_mode.clone();
+                config.choices_count = self.choices_count;
+                config.retry_missed = self.retry_missed;
+                config.pause_on_focus_lost = self.pause_on_focus_lost;
+                config.kana_selection = self.kana_selection.clone();
                 let _ = config.save(); // should show popup if failed to save?
                 return PageEvent::Navigate(Homepage::default().into());
             }
@@ -99,51 +228,34 @@ impl IPage for ConfigPage {
 
         PageEvent::Nothing
     }
-}
 
-impl ConfigPage {
-    fn bold_kana_field(&self, width: usize, is_focused: bool) -> Line {
-        let label = Span::from("Kana in bold");
-        let is_checked = self.study_bold_kana;
-        let value = if is_checked { " \u{02713} " } else { "   " };
-        let mut checkbox = Span::from(value).bold();
-        if is_focused {
-            checkbox = checkbox.black().on_light_red();
+    /// A click on a field changes its value, a click on a button presses it.
+    fn handle_mouse_events(&mut self, mouse_event: MouseEvent, config: &mut Config) -> PageEvent {
+        let Some(position) = app::left_click(&mouse_event) else {
+            return app::scroll_with_arrows(self, mouse_event, config);
+        };
+        let clicked_field = if self.field_list_area.contains(position) {
+            let row = usize::from(position.y - self.field_list_area.y);
+            std::iter::successors(Some(ConfigField::BoldKana), ConfigField::down)
+                .take_while(|field| !matches!(field, ConfigField::Action(_)))
+                .nth(row)
+        } else if self.button_areas[0].contains(position) {
+            Some(ConfigField::Action(BottomAction::Cancel))
+        } else if self.button_areas[1].contains(position) {
+            Some(ConfigField::Action(BottomAction::Save))
         } else {
-            checkbox = checkbox.on_dark_gray();
-        }
-
-        let space_available = width
-            .saturating_sub(label.width())
-            .saturating_sub(checkbox.width());
-        Line::from(Vec::from([
-            label,
-            Span::from(" ".repeat(space_available)),
-            checkbox,
-        ]))
-    }
-
-    fn writing_system_field(&self, width: usize, is_focused: bool) -> Line {
-        let label = Span::from("Writing system");
-        let input_value = KanaRepresentation::from(self.writing_system.clone()).to_string();
-        let mut input = Span::from(format!("\u{2BC7} {} \u{2BC8}", input_value)).bold();
-        if is_focused {
-            input = input.black().on_light_red();
-        } else {
-            input = input.white().on_dark_gray();
-        }
-
-        let space_available = width
-            .saturating_sub(label.width())
-            .saturating_sub(input.width());
-        Line::from(Vec::from([
-            label,
-            Span::from(" ".repeat(space_available)),
-            input,
-        ]))
+            None
+        };
+        let Some(field) = clicked_field else {
+            return PageEvent::Nothing;
+        };
+        self.focused_field = field;
+        self.handle_key_events(KeyEvent::from(KeyCode::Enter), config)
     }
+}
 
-    fn render_bottom_actions(&self, frame: &mut Frame, area: Rect) {
+impl ConfigPage {
+    fn render_bottom_actions(&mut self, frame: &mut Frame, area: Rect) {
         let [left_button, _, right_button] = Layout::horizontal([
             Constraint::Fill(1),
             Constraint::Length(3),
@@ -166,14 +278,76 @@ impl ConfigPage {
             ),
             right_button,
         );
+
+        // the buttons are their label with a space on each side
+        let cancel_width = left_button.width.min(8);
+        let save_width = right_button.width.min(6);
+        self.button_areas = [
+            Rect {
+                x: left_button.right() - cancel_width,
+                width: cancel_width,
+                ..left_button
+            },
+            Rect {
+                width: save_width,
+                ..right_button
+            },
+        ];
+    }
+}
+
+fn checkbox_field(label: &str, is_checked: bool, width: usize, is_focused: bool) -> Line<'_> {
+    let label = Span::from(label);
+    let value = if is_checked { " \u{02713} " } else { "   " };
+    let mut checkbox = Span::from(value).bold();
+    if is_focused {
+        checkbox = checkbox.black().on_light_red();
+    } else {
+        checkbox = checkbox.on_dark_gray();
+    }
+
+    let space_available = width
+        .saturating_sub(label.width())
+        .saturating_sub(checkbox.width());
+    Line::from(Vec::from([
+        label,
+        Span::from(" ".repeat(space_available)),
+        checkbox,
+    ]))
+}
+
+fn input_field(label: &str, value: String, width: usize, is_focused: bool) -> Line<'_> {
+    let label = Span::from(label);
+    let mut input = Span::from(value).bold();
+    if is_focused {
+        input = input.black().on_light_red();
+    } else {
+        input = input.white().on_dark_gray();
     }
+
+    let space_available = width
+        .saturating_sub(label.width())
+        .saturating_sub(input.width());
+    Line::from(Vec::from([
+        label,
+        Span::from(" ".repeat(space_available)),
+        input,
+    ]))
 }
 
-#[derive(Debug, Default, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq)]
 enum ConfigField {
     #[default]
     BoldKana,
+    BigKana,
     WritingSystemField,
+    StudyDirectionField,
+    RomanizationField,
+    AnswerModeField,
+    ChoicesCount,
+    RetryMissed,
+    PauseOnFocusLost,
+    KanaSelection,
     Action(BottomAction),
 }
 
@@ -193,22 +367,47 @@ impl ConfigField {
     fn up(&self) -> Option<Self> {
         match self {
             Self::BoldKana => None,
-            Self::WritingSystemField => Some(Self::BoldKana),
-            Self::Action(_) => Some(Self::WritingSystemField),
+            Self::BigKana => Some(Self::BoldKana),
+            Self::WritingSystemField => Some(Self::BigKana),
+            Self::StudyDirectionField => Some(Self::WritingSystemField),
+            Self::RomanizationField => Some(Self::StudyDirectionField),
+            Self::AnswerModeField => Some(Self::RomanizationField),
+            Self::ChoicesCount => Some(Self::AnswerModeField),
+            Self::RetryMissed => Some(Self::ChoicesCount),
+            Self::PauseOnFocusLost => Some(Self::RetryMissed),
+            Self::KanaSelection => Some(Self::PauseOnFocusLost),
+            Self::Action(_) => Some(Self::KanaSelection),
         }
     }
 
     fn down(&self) -> Option<Self> {
         match self {
-            Self::BoldKana => Some(Self::WritingSystemField),
-            Self::WritingSystemField => Some(Self::Action(BottomAction::Save)),
+            Self::BoldKana => Some(Self::BigKana),
+            Self::BigKana => Some(Self::WritingSystemField),
+            Self::WritingSystemField => Some(Self::StudyDirectionField),
+            Self::StudyDirectionField => Some(Self::RomanizationField),
+            Self::RomanizationField => Some(Self::AnswerModeField),
+            Self::AnswerModeField => Some(Self::ChoicesCount),
+            Self::ChoicesCount => Some(Self::RetryMissed),
+            Self::RetryMissed => Some(Self::PauseOnFocusLost),
+            Self::PauseOnFocusLost => Some(Self::KanaSelection),
+            Self::KanaSelection => Some(Self::Action(BottomAction::Save)),
             Self::Action(_) => None,
         }
     }
 
     fn right(&self) -> Option<Self> {
         match self {
-            Self::BoldKana | Self::WritingSystemField => self.down(),
+            Self::BoldKana
+            | Self::BigKana
+            | Self::WritingSystemField
+            | Self::StudyDirectionField
+            | Self::RomanizationField
+            | Self::AnswerModeField
+            | Self::ChoicesCount
+            | Self::RetryMissed
+            | Self::PauseOnFocusLost
+            | Self::KanaSelection => self.down(),
             Self::Action(BottomAction::Cancel) => Some(Self::Action(BottomAction::Save)),
             Self::Action(BottomAction::Save) => Some(Self::Action(BottomAction::Cancel)),
         }
@@ -216,14 +415,32 @@ impl ConfigField {
 
     fn left(&self) -> Option<Self> {
         match self {
-            Self::BoldKana | Self::WritingSystemField => self.up(),
+            Self::BoldKana
+            | Self::BigKana
+            | Self::WritingSystemField
+            | Self::StudyDirectionField
+            | Self::RomanizationField
+            | Self::AnswerModeField
+            | Self::ChoicesCount
+            | Self::RetryMissed
+            | Self::PauseOnFocusLost
+            | Self::KanaSelection => self.up(),
             Self::Action(_) => self.right(),
         }
     }
 
     fn tab(&self) -> Option<Self> {
         match self {
-            Self::BoldKana | Self::WritingSystemField => self.down(),
+            Self::BoldKana
+            | Self::BigKana
+            | Self::WritingSystemField
+            | Self::StudyDirectionField
+            | Self::RomanizationField
+            | Self::AnswerModeField
+            | Self::ChoicesCount
+            | Self::RetryMissed
+            | Self::PauseOnFocusLost
+            | Self::KanaSelection => self.down(),
             Self::Action(BottomAction::Cancel) => Some(Self::BoldKana),
             Self::Action(BottomAction::Save) => self.left(),
         }
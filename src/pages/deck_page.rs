@@ -0,0 +1,164 @@
+use super::{Homepage, StudyPage};
+use crate::{
+    app::{self, IPage, PageEvent},
+    config::Config,
+    models::{
+        deck::{Deck, DeckError, DECKS_FOLDER},
+        word::{session_words, JlptLevel, Word},
+    },
+};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use ratatui::{
+    layout::{Constraint, Layout, Margin, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{List, ListState, Paragraph, Wrap},
+    Frame,
+};
+use std::path::PathBuf;
+use strum::IntoEnumIterator;
+
+/// Pick the bundled words, or a deck of the decks folder, to study.
+#[derive(Debug)]
+pub struct DeckPage {
+    /// Levels of the bundled words, listed before the decks.
+    word_levels: Vec<JlptLevel>,
+    decks: Vec<(PathBuf, Result<Deck, DeckError>)>,
+    decks_state: ListState,
+    /// Where the list was rendered, to click on its items.
+    list_area: Rect,
+}
+
+impl Default for DeckPage {
+    fn default() -> Self {
+        Self {
+            word_levels: JlptLevel::iter().collect(),
+            decks: Deck::load_all(),
+            decks_state: ListState::default().with_selected(Some(0)),
+            list_area: Rect::default(),
+        }
+    }
+}
+
+impl IPage for DeckPage {
+    fn render(&mut self, frame: &mut Frame, main_area: Rect, _: &Config) {
+        let [title_area, middle_area, help_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(2),
+        ])
+        .areas(main_area.inner(Margin::new(1, 0)));
+
+        let title = Line::from("\u{1F0CF} Decks").bold().centered();
+        frame.render_widget(title, title_area);
+
+        let word_lines = self.word_levels.iter().map(|level| {
+            Line::from(Vec::from([
+                Span::from(format!("{} words", level)),
+                Span::from(format!(" {}", Word::bundled(*level).len())).dim(),
+            ]))
+        });
+        let deck_lines = self.decks.iter().map(|(path, deck)| match deck {
+            Ok(deck) => Line::from(Vec::from([
+                Span::from(deck.name.clone()),
+                Span::from(format!(" {}", deck.cards.len())).dim(),
+            ])),
+            Err(_) => Line::from(format!(
+                "{} (invalid)",
+                path.file_name().unwrap_or_default().to_string_lossy()
+            ))
+            .light_red(),
+        });
+        let lines: Vec<Line> = word_lines.chain(deck_lines).collect();
+        let [list_area, empty_area] = Layout::vertical([
+            Constraint::Length(lines.len() as u16 + 1),
+            Constraint::Fill(1),
+        ])
+        .areas(middle_area);
+        let list = List::new(lines).highlight_style(Style::default().black().on_light_red());
+        frame.render_stateful_widget(list, list_area, &mut self.decks_state);
+        self.list_area = list_area;
+
+        if self.decks.is_empty() {
+            let empty = Paragraph::new(format!(
+                "No deck yet, add .toml files to {}",
+                DECKS_FOLDER.display()
+            ))
+            .dim()
+            .centered()
+            .wrap(Wrap { trim: true });
+            frame.render_widget(empty, empty_area);
+        }
+
+        // the error of an invalid deck is shown instead of the help
+        let help = match self.selected_deck() {
+            Some((_, Err(e))) => Paragraph::new(e.to_string())
+                .light_red()
+                .wrap(Wrap { trim: true }),
+            _ => Paragraph::new(Vec::from([
+                Line::from("enter to study"),
+                Line::from("esc to go back"),
+            ]))
+            .dim()
+            .centered(),
+        };
+        frame.render_widget(help, help_area);
+    }
+
+    fn handle_key_events(&mut self, key_event: KeyEvent, config: &mut Config) -> PageEvent {
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                return PageEvent::Navigate(Homepage::default().into());
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                let study_page = match (self.selected_word_level(), self.selected_deck()) {
+                    (Some(level), _) => StudyPage::from_words(session_words(
+                        level,
+                        &config.writing_system,
+                        &mut rand::rng(),
+                    )),
+                    (_, Some((_, Ok(deck)))) => StudyPage::from_deck(deck.clone()),
+                    _ => None,
+                };
+                if let Some(study_page) = study_page {
+                    return PageEvent::Navigate(study_page.into());
+                }
+            }
+            KeyCode::Up => self.decks_state.select_previous(),
+            KeyCode::Down => self.decks_state.select_next(),
+            _ => {}
+        }
+
+        PageEvent::Nothing
+    }
+
+    /// A click on a deck studies it.
+    fn handle_mouse_events(&mut self, mouse_event: MouseEvent, config: &mut Config) -> PageEvent {
+        let Some(position) = app::left_click(&mouse_event) else {
+            return app::scroll_with_arrows(self, mouse_event, config);
+        };
+        if !self.list_area.contains(position) {
+            return PageEvent::Nothing;
+        }
+        let index = self.decks_state.offset() + usize::from(position.y - self.list_area.y);
+        if index >= self.word_levels.len() + self.decks.len() {
+            return PageEvent::Nothing;
+        }
+        self.decks_state.select(Some(index));
+        self.handle_key_events(KeyEvent::from(KeyCode::Enter), config)
+    }
+}
+
+impl DeckPage {
+    fn selected_word_level(&self) -> Option<JlptLevel> {
+        self.decks_state
+            .selected()
+            .and_then(|i| self.word_levels.get(i).copied())
+    }
+
+    fn selected_deck(&self) -> Option<&(PathBuf, Result<Deck, DeckError>)> {
+        self.decks_state
+            .selected()
+            .and_then(|i| self.decks.get(i.checked_sub(self.word_levels.len())?))
+    }
+}
@@ -0,0 +1,217 @@
+use super::Homepage;
+use crate::{
+    app::{IPage, PageEvent},
+    config::{Config, WritingSystem},
+    models::{
+        answer::AnswerResult,
+        history::{History, SessionRecord},
+    },
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout, Margin, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{List, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+/// Browse the past study sessions, and see the details of one session.
+#[derive(Debug, Clone)]
+pub struct HistoryPage {
+    /// From the newest to the oldest.
+    sessions: Vec<SessionRecord>,
+    load_error: Option<String>,
+    sessions_state: ListState,
+    /// Is set when we are looking at the details of the selected session.
+    details_state: Option<ListState>,
+}
+
+impl Default for HistoryPage {
+    fn default() -> Self {
+        let (sessions, load_error) = match History::load() {
+            Ok(history) => (history.sessions.into_iter().rev().collect(), None),
+            Err(e) => (Vec::new(), Some(e.to_string())),
+        };
+        Self {
+            sessions,
+            load_error,
+            sessions_state: ListState::default().with_selected(Some(0)),
+            details_state: None,
+        }
+    }
+}
+
+impl IPage for HistoryPage {
+    fn render(&mut self, frame: &mut Frame, main_area: Rect, _: &Config) {
+        let [title_area, middle_area, help_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(2),
+        ])
+        .areas(main_area.inner(Margin::new(1, 0)));
+
+        let selected_session = self
+            .sessions_state
+            .selected()
+            .and_then(|i| self.sessions.get(i));
+        match (selected_session, &mut self.details_state) {
+            (Some(session), Some(details_state)) => {
+                let title = Line::from(session.timestamp.format("%Y-%m-%d %H:%M").to_string())
+                    .bold()
+                    .centered();
+                frame.render_widget(title, title_area);
+                render_session_details(frame, middle_area, session, details_state);
+            }
+            _ => {
+                let title = Line::from("\u{1F4DC} History").bold().centered();
+                frame.render_widget(title, title_area);
+                self.render_sessions(frame, middle_area);
+            }
+        }
+
+        let help = if self.details_state.is_some() {
+            Vec::from([Line::from("arrows to scroll"), Line::from("esc to go back")])
+        } else {
+            Vec::from([
+                Line::from("enter to see details"),
+                Line::from("esc to go back"),
+            ])
+        };
+        frame.render_widget(Paragraph::new(help).dim().centered(), help_area);
+    }
+
+    fn handle_key_events(&mut self, key_event: KeyEvent, _: &mut Config) -> PageEvent {
+        if let Some(ref mut details_state) = self.details_state {
+            let offset = details_state.offset_mut();
+            match key_event.code {
+                KeyCode::Esc | KeyCode::Char('q') => self.details_state = None,
+                KeyCode::Up => *offset = offset.saturating_sub(1),
+                KeyCode::Down => *offset += 1, // clamped when rendering
+                _ => {}
+            }
+            return PageEvent::Nothing;
+        }
+
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                return PageEvent::Navigate(Homepage::default().into());
+            }
+            KeyCode::Enter | KeyCode::Char(' ') if !self.sessions.is_empty() => {
+                self.details_state = Some(ListState::default());
+            }
+            KeyCode::Up => self.sessions_state.select_previous(),
+            KeyCode::Down => self.sessions_state.select_next(),
+            _ => {}
+        }
+
+        PageEvent::Nothing
+    }
+}
+
+impl HistoryPage {
+    fn render_sessions(&mut self, frame: &mut Frame, area: Rect) {
+        if let Some(ref load_error) = self.load_error {
+            let error = Paragraph::new(load_error.as_str())
+                .light_red()
+                .wrap(Wrap { trim: true });
+            frame.render_widget(error, area);
+            return;
+        }
+        if self.sessions.is_empty() {
+            let empty = Paragraph::new("No session yet, go study!")
+                .dim()
+                .centered()
+                .wrap(Wrap { trim: true });
+            frame.render_widget(empty, area);
+            return;
+        }
+
+        let lines: Vec<Line> = self
+            .sessions
+            .iter()
+            .map(|session| {
+                Line::from(format!(
+                    "{} {} {:>3}/{:<3} {}",
+                    session.timestamp.format("%m/%d %H:%M"),
+                    writing_system_glyph(&session.writing_system),
+                    session.get_count_by_result(&AnswerResult::Good),
+                    session.answers.len(),
+                    format_timer(session.elapsed_ms),
+                ))
+            })
+            .collect();
+        let list = List::new(lines).highlight_style(Style::default().black().on_light_red());
+        frame.render_stateful_widget(list, area, &mut self.sessions_state);
+    }
+}
+
+fn render_session_details(
+    frame: &mut Frame,
+    area: Rect,
+    session: &SessionRecord,
+    details_state: &mut ListState,
+) {
+    let [summary_area, answers_area] =
+        Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]).areas(area);
+
+    let summary = Line::from(format!(
+        "{}: {}/{} in {}",
+        session.writing_system,
+        session.get_count_by_result(&AnswerResult::Good),
+        session.answers.len(),
+        format_timer(session.elapsed_ms),
+    ))
+    .centered();
+    frame.render_widget(summary, summary_area);
+
+    let lines: Vec<Line> = session
+        .answers
+        .iter()
+        .map(|answer| {
+            let mut line = Line::from(Vec::from([
+                Span::from(match answer.result {
+                    AnswerResult::Good => "\u{2714} ",
+                    AnswerResult::Wrong => "\u{2717} ",
+                }),
+                Span::from(format!(
+                    "{} ",
+                    answer.kana.to_representation(&answer.representation)
+                ))
+                .bold(),
+                Span::from(format!("{:<5}", answer.kana)),
+            ]));
+            // sessions saved by older versions have no details
+            if answer.response_ms > 0 {
+                line.push_span(
+                    Span::from(format!(
+                        "{:.1}s {}",
+                        answer.response_ms as f64 / 1000_f64,
+                        answer.input
+                    ))
+                    .dim(),
+                );
+            }
+            match answer.result {
+                AnswerResult::Good => line,
+                AnswerResult::Wrong => line.light_red(),
+            }
+        })
+        .collect();
+    frame.render_stateful_widget(List::new(lines), answers_area, details_state);
+}
+
+fn writing_system_glyph(writing_system: &WritingSystem) -> &'static str {
+    match writing_system {
+        WritingSystem::Hiragana => "\u{3042}",
+        WritingSystem::Katakana => "\u{30A2}",
+        // 両 (ryō), "both"
+        WritingSystem::Both => "\u{4E21}",
+    }
+}
+
+fn format_timer(elapsed_ms: u64) -> String {
+    let seconds = (elapsed_ms / 1000) % 60;
+    let minutes = elapsed_ms / 60_000;
+    format!("{:02}:{:02}", minutes, seconds)
+}
@@ -1,36 +1,71 @@
-use super::{ConfigPage, StudyPage};
+use super::{ConfigPage, DeckPage, HistoryPage, StatsPage, StudyPage};
 use crate::{
-    app::{IPage, PageEvent},
+    app::{self, IPage, PageEvent},
     config::Config,
+    models::{create_review_plan, create_study_plan, session::SavedSession, srs::ReviewSchedule},
     tui,
     widgets::{Menu, MenuState},
 };
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
 use ratatui::{
     layout::{Constraint, Flex, Layout, Margin, Rect},
     style::Stylize,
-    text::ToLine,
+    text::{Line, ToLine},
     widgets::{Paragraph, Wrap},
     Frame,
 };
-use strum::{EnumCount, IntoStaticStr, VariantArray};
+use strum::{IntoStaticStr, VariantArray};
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct Homepage {
     menu_state: MenuState,
+    /// Options of the menu, [`MenuOption::Resume`] is only there with a saved session.
+    menu_options: Vec<MenuOption>,
+    /// Shown instead of the slogan, e.g. when we can't start a study.
+    notice: Option<&'static str>,
+    /// Where the menu was rendered, with the spacing of its options, to click on them.
+    menu_area: Rect,
+    menu_spacing: u16,
+}
+
+impl Default for Homepage {
+    fn default() -> Self {
+        let has_saved_session = SavedSession::exists();
+        Self {
+            menu_state: MenuState::default(),
+            menu_options: MenuOption::VARIANTS
+                .iter()
+                .filter(|option| has_saved_session || **option != MenuOption::Resume)
+                .cloned()
+                .collect(),
+            notice: None,
+            menu_area: Rect::default(),
+            menu_spacing: 1,
+        }
+    }
 }
 
 impl IPage for Homepage {
     fn render(&mut self, frame: &mut Frame, main_area: Rect, _: &Config) {
-        let [slogan_area, menu_area] = Layout::vertical([Constraint::Fill(1), Constraint::Fill(1)])
-            .areas(main_area.inner(Margin::new(3, 0)));
+        let options_count = self.menu_options.len() as u16;
+        // the options are packed together on small terminals
+        let menu_spacing = u16::from(main_area.height >= options_count * 2 - 1 + 2);
+        let menu_height = options_count + (options_count - 1) * menu_spacing;
+        // the margin is dropped when the slogan (2 lines) would not fit above the menu
+        let vertical_margin = main_area.height.saturating_sub(menu_height + 2).min(2) / 2;
+        let [slogan_area, menu_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(menu_height)])
+                .areas(main_area.inner(Margin::new(3, vertical_margin)));
 
-        let slogan = Paragraph::new(Vec::from(["Learn your kanas from the terminal. \u{1F5FF}"
-            .to_line()
-            .gray()
-            .italic()
-            .centered()]))
-        .wrap(Wrap { trim: true });
+        let slogan_line = match self.notice {
+            Some(notice) => Line::from(notice).light_red().centered(),
+            None => "Learn your kanas from the terminal. \u{1F5FF}"
+                .to_line()
+                .gray()
+                .italic()
+                .centered(),
+        };
+        let slogan = Paragraph::new(Vec::from([slogan_line])).wrap(Wrap { trim: true });
         frame.render_widget(
             slogan,
             tui::flex(
@@ -40,46 +75,100 @@ impl IPage for Homepage {
             ),
         );
 
-        let menu = Menu::new(MenuOption::to_vec_str()).centered();
+        let options = self.menu_options.iter().map(<&str>::from).collect();
+        self.menu_area = menu_area;
+        self.menu_spacing = menu_spacing;
+        let menu = Menu::new(options)
+            .with_spacing(usize::from(menu_spacing))
+            .centered();
         frame.render_stateful_widget(menu, menu_area, &mut self.menu_state);
     }
 
     fn handle_key_events(&mut self, key_event: KeyEvent, config: &mut Config) -> PageEvent {
-        let menu_option = &MenuOption::VARIANTS[self.menu_state.current_option];
+        let menu_option = &self.menu_options[self.menu_state.current_option];
+        let last_option = self.menu_options.len() - 1;
+        self.notice = None;
         match (menu_option, key_event.code) {
             (_, KeyCode::Esc | KeyCode::Char('q')) => return PageEvent::QuitApp,
             (MenuOption::Quit, KeyCode::Enter | KeyCode::Char(' ')) => return PageEvent::QuitApp,
+            (MenuOption::Resume, KeyCode::Enter | KeyCode::Char(' ')) => {
+                match SavedSession::load() {
+                    Ok(Some(session)) => {
+                        let _ = SavedSession::remove();
+                        return PageEvent::Navigate(StudyPage::from_saved(session).into());
+                    }
+                    _ => self.notice = Some("The session can't be resumed."),
+                }
+            }
             (MenuOption::Study, KeyCode::Enter | KeyCode::Char(' ')) => {
-                return PageEvent::Navigate(StudyPage::default().into());
+                match StudyPage::new(create_study_plan(config)) {
+                    Some(study_page) => return PageEvent::Navigate(study_page.into()),
+                    None => self.notice = Some("No kana to study, check your configuration."),
+                }
+            }
+            (MenuOption::Review, KeyCode::Enter | KeyCode::Char(' ')) => {
+                let schedule = ReviewSchedule::load().unwrap_or_default();
+                match StudyPage::new(create_review_plan(&schedule)) {
+                    Some(study_page) => return PageEvent::Navigate(study_page.into()),
+                    None => self.notice = Some("Nothing to review, come back later!"),
+                }
+            }
+            (MenuOption::Decks, KeyCode::Enter | KeyCode::Char(' ')) => {
+                return PageEvent::Navigate(DeckPage::default().into());
+            }
+            (MenuOption::History, KeyCode::Enter | KeyCode::Char(' ')) => {
+                return PageEvent::Navigate(HistoryPage::default().into());
+            }
+            (MenuOption::Stats, KeyCode::Enter | KeyCode::Char(' ')) => {
+                return PageEvent::Navigate(StatsPage::default().into());
             }
             (MenuOption::Configure, KeyCode::Enter | KeyCode::Char(' ')) => {
                 return PageEvent::Navigate(ConfigPage::from(config.clone()).into());
             }
             (_, KeyCode::Right | KeyCode::Down) => {
-                self.menu_state.next_option(MenuOption::COUNT - 1);
+                self.menu_state.next_option(last_option);
             }
             (_, KeyCode::Left | KeyCode::Up) => {
-                self.menu_state.previous_option(MenuOption::COUNT - 1);
+                self.menu_state.previous_option(last_option);
             }
             _ => {}
         }
 
         PageEvent::Nothing
     }
+
+    /// The menu gets tighter, until it is only its options.
+    fn has_compact_layout(&self) -> bool {
+        true
+    }
+
+    /// A click on an option picks it.
+    fn handle_mouse_events(&mut self, mouse_event: MouseEvent, config: &mut Config) -> PageEvent {
+        let Some(position) = app::left_click(&mouse_event) else {
+            return app::scroll_with_arrows(self, mouse_event, config);
+        };
+        if !self.menu_area.contains(position) {
+            return PageEvent::Nothing;
+        }
+        let line = usize::from(position.y - self.menu_area.y);
+        let line_height = usize::from(self.menu_spacing) + 1;
+        let option = line / line_height;
+        if line % line_height != 0 || option >= self.menu_options.len() {
+            return PageEvent::Nothing;
+        }
+        self.menu_state.current_option = option;
+        self.handle_key_events(KeyEvent::from(KeyCode::Enter), config)
+    }
 }
 
-#[derive(Debug, Clone, EnumCount, VariantArray, IntoStaticStr)]
+#[derive(Debug, Clone, PartialEq, VariantArray, IntoStaticStr)]
 enum MenuOption {
+    Resume,
     Study,
+    Review,
+    Decks,
+    History,
+    Stats,
     Configure,
     Quit,
 }
-
-impl MenuOption {
-    fn to_vec_str<'a>() -> Vec<&'a str> {
-        Vec::from(MenuOption::VARIANTS)
-            .into_iter()
-            .map(MenuOption::into)
-            .collect()
-    }
-}
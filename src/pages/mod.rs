@@ -1,9 +1,17 @@
 mod config_page;
+mod deck_page;
+mod history_page;
 mod homepage;
 mod result_page;
+mod selection_page;
+mod stats_page;
 mod study_page;
 
 pub use config_page::*;
+pub use deck_page::*;
+pub use history_page::*;
 pub use homepage::*;
 pub use result_page::*;
+pub use selection_page::*;
+pub use stats_page::*;
 pub use study_page::*;
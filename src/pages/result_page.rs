@@ -1,101 +1,418 @@
 use super::Homepage;
 use crate::{
     app::{IPage, PageEvent},
-    config::Config,
-    models::{answer::AnswerResult, kana::KanaRepresentation},
+    config::{Config, Romanization},
+    models::{
+        answer::{Answer, AnswerResult},
+        deck::CardAnswer,
+        export::export_sessions,
+        history::SessionRecord,
+        kana::KanaRepresentation,
+        kana_group::KanaRow,
+        word::WordAnswer,
+        StudyItem,
+    },
 };
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     layout::{Constraint, Layout, Margin, Rect},
     style::Stylize,
-    text::{Line, Span, ToLine, ToSpan},
-    widgets::{Paragraph, Wrap},
+    text::{Line, Span},
+    widgets::{Cell, Paragraph, Row, Table, TableState},
     Frame,
 };
+use strum::IntoEnumIterator;
+use tui_prompts::{Prompt, State, TextPrompt, TextState};
 
 #[derive(Debug, Clone)]
 pub struct ResultPage {
-    good_answers_count: usize,
-    wrong_answers_count: usize,
-    total_elapsed_time: u128,
+    record: SessionRecord,
+    /// Answers of the cards, when a deck was studied.
+    card_answers: Vec<CardAnswer>,
+    /// Answers of the words, when words were studied.
+    word_answers: Vec<WordAnswer>,
+    /// Missed kanas (or cards, or words) studied again, and how many times until they were known.
+    retries: Vec<(StudyItem, usize)>,
+    /// Scroll of the answers table, clamped when rendering.
+    table_offset: usize,
+    /// Path of the export being typed, the format is chosen from its extension.
+    export_path: Option<TextState<'static>>,
+    /// Outcome of the last export.
+    notice: Option<String>,
 }
 
 impl IPage for ResultPage {
     fn render(&mut self, frame: &mut Frame, main_area: Rect, config: &Config) {
-        let [area_top, area_middle, area_bottom] = Layout::vertical([
-            Constraint::Length(3),
-            Constraint::Fill(1),
+        let summary = self.summary_lines();
+        let [area_top, area_summary, area_table, area_bottom] = Layout::vertical([
             Constraint::Length(2),
+            Constraint::Length(summary.len() as u16 + 1),
+            Constraint::Fill(1),
+            Constraint::Length(3),
         ])
         .areas(main_area.inner(Margin::new(1, 1)));
 
         let congratulations_line = Line::from("You finished! \u{1F44F}").bold().centered();
         frame.render_widget(congratulations_line, area_top);
+        frame.render_widget(Paragraph::new(summary).centered(), area_summary);
 
-        let kana_representation = KanaRepresentation::from(config.writing_system.clone());
-        let kanas_count = self.good_answers_count + self.wrong_answers_count;
-        let correct_percent = (self.good_answers_count as f64 / kanas_count as f64) * 100_f64;
-        let kanas_count_line = Line::from(Vec::from([
-            "You have completed your study plan of ".to_span(),
-            kanas_count.to_span().bold(),
-            Span::from(format!(" {}(s) in ", kana_representation)),
-            self.format_time().bold(),
-            ".".to_span(),
-        ]));
-        let goods_line = Line::from(Vec::from([
-            "> correct answers: ".to_span(),
-            self.good_answers_count.to_span(),
-            "/".to_span(),
-            kanas_count.to_span(),
-        ]));
-        let wrongs_line = Line::from(Vec::from([
-            "> wrong answers: ".to_span(),
-            self.wrong_answers_count.to_span(),
-            "/".to_span(),
-            kanas_count.to_span(),
-        ]));
-        let result_paragraph = Paragraph::new(Vec::from([
-            kanas_count_line,
-            "".to_line(),
-            "You had:".to_line(),
-            goods_line,
-            wrongs_line,
-            format!("Total: {:.0}% correct answers.", correct_percent).into(),
-        ]))
-        .wrap(Wrap { trim: true });
-        frame.render_widget(result_paragraph, area_middle);
+        let rows = self.table_rows(&config.romanization);
+        // the header takes one line
+        let max_offset = rows
+            .len()
+            .saturating_sub(usize::from(area_table.height.saturating_sub(1)));
+        self.table_offset = self.table_offset.min(max_offset);
+        let [shown_width, expected_width] = self.column_widths(&config.romanization);
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(shown_width),
+                Constraint::Length(expected_width),
+                Constraint::Length(3),
+                Constraint::Length(5),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(Row::new(["", "", "", "time", "typed"]).dim());
+        frame.render_stateful_widget(
+            table,
+            area_table,
+            &mut TableState::default().with_offset(self.table_offset),
+        );
 
-        let info = Paragraph::new("Press any key to go to the homepage.")
-            .wrap(Wrap { trim: true })
-            .centered()
-            .dim();
+        if let Some(export_path) = &mut self.export_path {
+            let [_, area_prompt, area_info] =
+                Layout::vertical([Constraint::Length(1); 3]).areas(area_bottom);
+            TextPrompt::from("export to").draw(frame, area_prompt, export_path);
+            let info = Line::from("enter to save, esc to cancel").centered().dim();
+            frame.render_widget(info, area_info);
+            return;
+        }
+        let info = Paragraph::new(Vec::from([
+            Line::from("arrows to scroll"),
+            match &self.notice {
+                Some(notice) => Line::from(notice.as_str()).italic(),
+                None if self.record.answers.is_empty() => Line::default(),
+                None => Line::from("e to export"),
+            },
+            Line::from("any other key to go home"),
+        ]))
+        .centered()
+        .dim();
         frame.render_widget(info, area_bottom);
     }
 
-    fn handle_key_events(&mut self, _: KeyEvent, _: &mut Config) -> PageEvent {
-        PageEvent::Navigate(Homepage::default().into())
+    fn handle_key_events(&mut self, key_event: KeyEvent, _: &mut Config) -> PageEvent {
+        self.notice = None;
+        if let Some(export_path) = &mut self.export_path {
+            match key_event.code {
+                KeyCode::Esc => self.export_path = None,
+                KeyCode::Enter => {
+                    let path = std::path::PathBuf::from(export_path.value());
+                    self.notice = Some(
+                        match export_sessions(std::slice::from_ref(&self.record), &path, None) {
+                            Ok(()) => format!("exported to {}", path.display()),
+                            Err(e) => e.to_string(),
+                        },
+                    );
+                    self.export_path = None;
+                }
+                _ => export_path.handle_key_event(key_event),
+            }
+            return PageEvent::Nothing;
+        }
+
+        match key_event.code {
+            KeyCode::Up => self.table_offset = self.table_offset.saturating_sub(1),
+            KeyCode::Down => self.table_offset += 1,
+            // only kanas are exported
+            KeyCode::Char('e') if !self.record.answers.is_empty() => {
+                let mut export_path = TextState::new()
+                    .with_focus(tui_prompts::FocusState::Focused)
+                    .with_value("results.csv");
+                export_path.move_end();
+                self.export_path = Some(export_path);
+            }
+            _ => return PageEvent::Navigate(Homepage::default().into()),
+        }
+
+        PageEvent::Nothing
     }
 }
 
 impl ResultPage {
+    fn summary_lines(&self) -> Vec<Line<'_>> {
+        let good_count = self.get_count_by_result(&AnswerResult::Good);
+        let answers_count = self.answers_count();
+        let correct_percent = (good_count as f64 / answers_count as f64) * 100_f64;
+        let mut lines = Vec::from([
+            Line::from(format!(
+                "{}/{} correct ({:.0}%)",
+                good_count, answers_count, correct_percent
+            ))
+            .bold(),
+            Line::from(format!("in {}", self.format_time())),
+        ]);
+        if let Some(average_response_ms) = self.average_response_ms() {
+            lines.push(Line::from(format!(
+                "{} per answer on average",
+                format_response_time(average_response_ms)
+            )));
+        }
+
+        // only break down the results when the session mixed scripts
+        let mut representations: Vec<&KanaRepresentation> = Vec::new();
+        for answer in &self.record.answers {
+            if !representations.contains(&&answer.representation) {
+                representations.push(&answer.representation);
+            }
+        }
+        if representations.len() > 1 {
+            let script_results: Vec<String> = representations
+                .iter()
+                .map(|representation| {
+                    let answers = self
+                        .record
+                        .answers
+                        .iter()
+                        .filter(|answer| answer.representation.eq(representation));
+                    format!(
+                        "{} {}/{}",
+                        representation,
+                        answers
+                            .clone()
+                            .filter(|answer| answer.result == AnswerResult::Good)
+                            .count(),
+                        answers.count()
+                    )
+                })
+                .collect();
+            lines.push(Line::from(script_results.join(", ")).dim());
+        }
+
+        lines
+    }
+
+    /// One row per answer, kanas are grouped by row of the kana table.
+    fn table_rows(&self, romanization: &Romanization) -> Vec<Row<'static>> {
+        let mut rows = Vec::new();
+        for kana_row in KanaRow::iter() {
+            let mut answers: Vec<&Answer> = self
+                .record
+                .answers
+                .iter()
+                .filter(|answer| answer.kana.row() == kana_row)
+                .collect();
+            if answers.is_empty() {
+                continue;
+            }
+            answers.sort_by_key(|answer| {
+                let position = kana_row.kanas().iter().position(|k| k.eq(&answer.kana));
+                (
+                    position,
+                    answer.representation == KanaRepresentation::Katakana,
+                )
+            });
+
+            rows.push(Row::new([Cell::from(
+                Line::from(kana_row.to_string()).dim(),
+            )]));
+            rows.extend(answers.into_iter().map(|answer| {
+                let item = StudyItem::Kana(answer.kana.clone(), answer.representation.clone());
+                answer_row(
+                    answer
+                        .kana
+                        .to_representation(&answer.representation)
+                        .to_string()
+                        .into(),
+                    answer.kana.romaji_in(romanization).to_string(),
+                    &answer.result,
+                    self.format_retries(&item),
+                    answer.response_ms,
+                    format_input(&answer.input, answer.attempts, answer.help_used),
+                )
+            }));
+        }
+
+        rows.extend(self.card_answers.iter().map(|answer| {
+            let item = StudyItem::Card(answer.card.clone());
+            answer_row(
+                answer.card.display.clone().into(),
+                answer.card.first_answer().to_string(),
+                &answer.result,
+                self.format_retries(&item),
+                answer.response_ms,
+                format_input(&answer.input, answer.attempts, answer.help_used),
+            )
+        }));
+
+        rows.extend(self.word_answers.iter().map(|answer| {
+            let item = StudyItem::Word(answer.word.clone());
+            answer_row(
+                word_line(answer),
+                answer.word.romaji_in(romanization),
+                &answer.result,
+                self.format_retries(&item),
+                answer.response_ms,
+                format_input(&answer.input, answer.attempts, answer.help_used),
+            )
+        }));
+
+        rows
+    }
+
+    /// Widths of the shown and expected columns, wide enough for the cards and words.
+    fn column_widths(&self, romanization: &Romanization) -> [u16; 2] {
+        let cards = self.card_answers.iter().map(|answer| {
+            (
+                Line::from(answer.card.display.as_str()).width(),
+                answer.card.first_answer().len(),
+            )
+        });
+        let words = self.word_answers.iter().map(|answer| {
+            (
+                Line::from(answer.word.to_string()).width(),
+                answer.word.romaji_in(romanization).len(),
+            )
+        });
+        let (shown_width, expected_width) = cards.chain(words).fold(
+            (4, 4),
+            |(shown, expected), (shown_width, expected_width)| {
+                (shown.max(shown_width), expected.max(expected_width))
+            },
+        );
+        [shown_width as u16, expected_width as u16]
+    }
+
+    /// How many times the item was retried, if it was.
+    fn format_retries(&self, item: &StudyItem) -> String {
+        self.retries
+            .iter()
+            .find(|(retried, _)| retried.eq(item))
+            .map(|(_, count)| format!("\u{00D7}{}", count))
+            .unwrap_or_default()
+    }
+
+    fn average_response_ms(&self) -> Option<u64> {
+        let total_response_ms: u64 = self
+            .record
+            .answers
+            .iter()
+            .map(|a| a.response_ms)
+            .chain(self.card_answers.iter().map(|a| a.response_ms))
+            .chain(self.word_answers.iter().map(|a| a.response_ms))
+            .sum();
+        total_response_ms.checked_div(self.answers_count() as u64)
+    }
+
+    fn answers_count(&self) -> usize {
+        self.record.answers.len() + self.card_answers.len() + self.word_answers.len()
+    }
+
+    fn get_count_by_result(&self, result: &AnswerResult) -> usize {
+        self.record
+            .answers
+            .iter()
+            .map(|answer| &answer.result)
+            .chain(self.card_answers.iter().map(|answer| &answer.result))
+            .chain(self.word_answers.iter().map(|answer| &answer.result))
+            .filter(|answer_result| answer_result.eq(&result))
+            .count()
+    }
+
     fn format_time(&self) -> String {
-        let seconds = (self.total_elapsed_time / 1000) % 60;
-        let minutes = (self.total_elapsed_time / 60_000) % 60;
+        let seconds = (self.record.elapsed_ms / 1000) % 60;
+        let minutes = (self.record.elapsed_ms / 60_000) % 60;
         if minutes == 0 {
             return format!("{}s", seconds);
         } else if seconds == 0 {
-            return format!("{}min", seconds);
+            return format!("{}min", minutes);
         }
         format!("{}min and {}s", minutes, seconds)
     }
 }
 
-impl From<super::study_page::StudyPage> for ResultPage {
-    fn from(value: super::study_page::StudyPage) -> Self {
+fn format_response_time(response_ms: u64) -> String {
+    format!("{:.1}s", response_ms as f64 / 1000_f64)
+}
+
+/// The word, with the kanas missed in the first answer underlined.
+fn word_line(answer: &WordAnswer) -> Line<'static> {
+    let spans: Vec<Span> = answer
+        .word
+        .kanas
+        .iter()
+        .enumerate()
+        .map(|(position, kana)| {
+            let span = Span::from(
+                kana.to_representation(&answer.word.representation)
+                    .to_string(),
+            );
+            if answer.wrong_kanas.contains(&position) {
+                span.underlined()
+            } else {
+                span
+            }
+        })
+        .collect();
+    Line::from(spans)
+}
+
+/// Wrong answers are highlighted.
+fn answer_row(
+    shown: Line<'static>,
+    expected: String,
+    result: &AnswerResult,
+    retries: String,
+    response_ms: u64,
+    input: String,
+) -> Row<'static> {
+    let row = Row::new([
+        Cell::from(shown).bold(),
+        Cell::from(expected),
+        Cell::from(match result {
+            AnswerResult::Good => format!("\u{2714}{}", retries),
+            AnswerResult::Wrong => format!("\u{2717}{}", retries),
+        }),
+        Cell::from(format_response_time(response_ms)),
+        Cell::from(input).dim(),
+    ]);
+    match result {
+        AnswerResult::Good => row,
+        AnswerResult::Wrong => row.light_red(),
+    }
+}
+
+/// What was typed, with the number of attempts in parentheses and whether help was used.
+fn format_input(input: &str, attempts: u32, help_used: bool) -> String {
+    let mut parts = Vec::new();
+    if !input.is_empty() {
+        parts.push(input.to_string());
+    }
+    if attempts > 1 {
+        parts.push(format!("({})", attempts));
+    }
+    if help_used {
+        parts.push(String::from("help"));
+    }
+    parts.join(" ")
+}
+
+impl ResultPage {
+    pub fn new(
+        record: SessionRecord,
+        card_answers: Vec<CardAnswer>,
+        word_answers: Vec<WordAnswer>,
+        retries: Vec<(StudyItem, usize)>,
+    ) -> Self {
         Self {
-            total_elapsed_time: value.total_elapsed_time_ms(),
-            good_answers_count: value.get_count_by_result(&AnswerResult::Good),
-            wrong_answers_count: value.get_count_by_result(&AnswerResult::Wrong),
+            record,
+            card_answers,
+            word_answers,
+            retries,
+            table_offset: 0,
+            export_path: None,
+            notice: None,
         }
     }
 }
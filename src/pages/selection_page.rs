@@ -0,0 +1,289 @@
+use super::ConfigPage;
+use crate::{
+    app::{self, IPage, PageEvent},
+    config::Config,
+    models::{
+        kana::{Kana, KanaRepresentation},
+        kana_group::{KanaGroup, KanaRow},
+    },
+};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use ratatui::{
+    layout::{Constraint, Layout, Margin, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{List, Paragraph},
+    Frame,
+};
+use strum::IntoEnumIterator;
+
+/// Choose the kanas to study, by group, by row or one by one.
+///
+/// The selection is given back to the [`ConfigPage`], and is only saved with it.
+#[derive(Debug, Clone)]
+pub struct SelectionPage {
+    config_page: ConfigPage,
+    selection: Vec<Kana>,
+    items: Vec<SelectionItem>,
+    focused_item: usize,
+    /// `0` is the whole row, then each kana of the row.
+    focused_column: usize,
+    scroll_offset: usize,
+    show_empty_warning: bool,
+    /// Where the list was rendered, to click on its items.
+    list_area: Rect,
+}
+
+impl From<ConfigPage> for SelectionPage {
+    fn from(value: ConfigPage) -> Self {
+        Self {
+            selection: value.kana_selection.clone(),
+            config_page: value,
+            items: SelectionItem::all(),
+            focused_item: 0,
+            focused_column: 0,
+            scroll_offset: 0,
+            show_empty_warning: false,
+            list_area: Rect::default(),
+        }
+    }
+}
+
+impl IPage for SelectionPage {
+    fn render(&mut self, frame: &mut Frame, main_area: Rect, _: &Config) {
+        let [title_area, list_area, help_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(3),
+        ])
+        .areas(main_area.inner(Margin::new(1, 0)));
+
+        let page_title = Line::from("Choose your kanas").bold().centered();
+        frame.render_widget(page_title, title_area);
+
+        // keep the focused item visible
+        let height = usize::from(list_area.height);
+        if self.focused_item < self.scroll_offset {
+            self.scroll_offset = self.focused_item;
+        } else if self.focused_item >= self.scroll_offset + height {
+            self.scroll_offset = self.focused_item + 1 - height;
+        }
+
+        let width = usize::from(list_area.width);
+        let lines: Vec<Line> = self
+            .items
+            .iter()
+            .enumerate()
+            .skip(self.scroll_offset)
+            .take(height)
+            .map(|(i, item)| {
+                let focused_column = (i == self.focused_item).then_some(self.focused_column);
+                match item {
+                    SelectionItem::Group(group) => self.group_line(group, width, focused_column),
+                    SelectionItem::Row(row) => self.row_line(row, focused_column),
+                }
+            })
+            .collect();
+        frame.render_widget(List::new(lines), list_area);
+        self.list_area = list_area;
+
+        let help = if self.show_empty_warning {
+            Paragraph::new("Select at least one kana.").light_red()
+        } else {
+            Paragraph::new(Vec::from([
+                Line::from("space to toggle"),
+                Line::from("enter to confirm"),
+                Line::from("esc to cancel"),
+            ]))
+            .dim()
+        };
+        frame.render_widget(help.centered(), help_area);
+    }
+
+    fn handle_key_events(&mut self, key_event: KeyEvent, _: &mut Config) -> PageEvent {
+        self.show_empty_warning = false;
+        match key_event.code {
+            KeyCode::Esc => return PageEvent::Navigate(self.config_page.clone().into()),
+            KeyCode::Enter => {
+                if self.selection.is_empty() {
+                    self.show_empty_warning = true;
+                } else {
+                    let mut config_page = self.config_page.clone();
+                    config_page.kana_selection = self.selection.clone();
+                    return PageEvent::Navigate(config_page.into());
+                }
+            }
+            KeyCode::Char(' ') => self.toggle_focused(),
+            KeyCode::Up => {
+                self.focused_item = self.focused_item.saturating_sub(1);
+                self.clamp_focused_column();
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                self.focused_item = (self.focused_item + 1).min(self.items.len() - 1);
+                self.clamp_focused_column();
+            }
+            KeyCode::Left => self.focused_column = self.focused_column.saturating_sub(1),
+            KeyCode::Right => {
+                self.focused_column += 1;
+                self.clamp_focused_column();
+            }
+            _ => {}
+        }
+
+        PageEvent::Nothing
+    }
+
+    /// A click on a group, a row or a kana toggles it.
+    fn handle_mouse_events(&mut self, mouse_event: MouseEvent, config: &mut Config) -> PageEvent {
+        let Some(position) = app::left_click(&mouse_event) else {
+            return app::scroll_with_arrows(self, mouse_event, config);
+        };
+        if !self.list_area.contains(position) {
+            return PageEvent::Nothing;
+        }
+        let item = self.scroll_offset + usize::from(position.y - self.list_area.y);
+        if item >= self.items.len() {
+            return PageEvent::Nothing;
+        }
+        self.focused_item = item;
+        self.focused_column = match self.items[item] {
+            SelectionItem::Group(_) => 0,
+            SelectionItem::Row(row) => {
+                Self::column_at(&self.row_line(&row, None), position.x - self.list_area.x)
+            }
+        };
+        self.show_empty_warning = false;
+        self.toggle_focused();
+        PageEvent::Nothing
+    }
+}
+
+impl SelectionPage {
+    fn clamp_focused_column(&mut self) {
+        let max_column = match self.items[self.focused_item] {
+            SelectionItem::Group(_) => 0,
+            SelectionItem::Row(row) => row.kanas().len(),
+        };
+        self.focused_column = self.focused_column.min(max_column);
+    }
+
+    /// Toggle the focused kana, or the whole row/group: if everything is already selected,
+    /// unselect everything, otherwise select everything.
+    fn toggle_focused(&mut self) {
+        let kanas = match (self.items[self.focused_item], self.focused_column) {
+            (SelectionItem::Row(row), column) if column > 0 => {
+                Vec::from([row.kanas()[column - 1].clone()])
+            }
+            (item, _) => item.kanas(),
+        };
+
+        if kanas.iter().all(|kana| self.selection.contains(kana)) {
+            self.selection.retain(|kana| !kanas.contains(kana));
+        } else {
+            self.selection.extend(kanas);
+            // keep the same order as the kana table
+            self.selection = Kana::iter()
+                .filter(|kana| self.selection.contains(kana))
+                .collect();
+        }
+    }
+
+    /// The column of a row line under `x`, the space before a kana belongs to it.
+    fn column_at(line: &Line, x: u16) -> usize {
+        let mut right = 0;
+        for (i, span) in line.spans.iter().enumerate() {
+            right += span.width();
+            if usize::from(x) < right {
+                return i.div_ceil(2);
+            }
+        }
+        line.spans.len() / 2
+    }
+
+    fn group_line(
+        &self,
+        group: &KanaGroup,
+        width: usize,
+        focused_column: Option<usize>,
+    ) -> Line<'_> {
+        let kanas = group.kanas();
+        let selected_count = kanas
+            .iter()
+            .filter(|kana| self.selection.contains(kana))
+            .count();
+
+        let mut label = Span::from(group.to_string()).bold();
+        if focused_column.is_some() {
+            label = label.black().on_light_red();
+        }
+        let count = Span::from(format!("{}/{}", selected_count, kanas.len())).dim();
+
+        let space_available = width
+            .saturating_sub(label.width())
+            .saturating_sub(count.width());
+        Line::from(Vec::from([
+            label,
+            Span::from(" ".repeat(space_available)),
+            count,
+        ]))
+    }
+
+    fn row_line(&self, row: &KanaRow, focused_column: Option<usize>) -> Line<'_> {
+        // extended katakana are never studied in hiragana
+        let representation = match row.group() {
+            KanaGroup::ExtendedKatakana => KanaRepresentation::Katakana,
+            _ => KanaRepresentation::from(self.config_page.writing_system.clone()),
+        };
+
+        let mut spans = Vec::new();
+        let is_row_selected = row.kanas().iter().all(|kana| self.selection.contains(kana));
+        let mut label = Span::from(format!("{:<2}", row));
+        label = if is_row_selected {
+            label.bold()
+        } else {
+            label.dim()
+        };
+        if focused_column == Some(0) {
+            label = label.black().on_light_red();
+        }
+        spans.push(label);
+
+        for (i, kana) in row.kanas().iter().enumerate() {
+            spans.push(Span::from(" "));
+            let style = if focused_column == Some(i + 1) {
+                Style::default().black().on_light_red()
+            } else if self.selection.contains(kana) {
+                Style::default().bold()
+            } else {
+                Style::default().dark_gray()
+            };
+            spans.push(Span::styled(kana.to_representation(&representation), style));
+        }
+
+        Line::from(spans)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SelectionItem {
+    Group(KanaGroup),
+    Row(KanaRow),
+}
+
+impl SelectionItem {
+    /// Each group followed by its rows.
+    fn all() -> Vec<Self> {
+        KanaGroup::iter()
+            .flat_map(|group| {
+                std::iter::once(Self::Group(group)).chain(group.rows().into_iter().map(Self::Row))
+            })
+            .collect()
+    }
+
+    fn kanas(&self) -> Vec<Kana> {
+        match self {
+            Self::Group(group) => group.kanas(),
+            Self::Row(row) => row.kanas().to_vec(),
+        }
+    }
+}
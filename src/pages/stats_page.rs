@@ -0,0 +1,274 @@
+use super::Homepage;
+use crate::{
+    app::{IPage, PageEvent},
+    config::Config,
+    models::{history::History, kana::KanaRepresentation, stats::Stats},
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Layout, Margin, Rect},
+    style::{Color, Style, Stylize},
+    symbols,
+    text::{Line, Span},
+    widgets::{
+        Axis, Bar, BarChart, BarGroup, Chart, Dataset, GraphType, Paragraph, Sparkline, Tabs, Wrap,
+    },
+    Frame,
+};
+use strum::{EnumCount, IntoStaticStr, VariantArray};
+
+/// Statistics of all the sessions saved in the history.
+#[derive(Debug, Clone)]
+pub struct StatsPage {
+    stats: Result<Stats, String>,
+    current_tab: usize,
+    /// First kana shown in the [`StatsTab::Kanas`] bar chart.
+    kanas_offset: usize,
+}
+
+impl Default for StatsPage {
+    fn default() -> Self {
+        Self {
+            stats: History::load()
+                .map(|history| Stats::from(&history))
+                .map_err(|e| e.to_string()),
+            current_tab: 0,
+            kanas_offset: 0,
+        }
+    }
+}
+
+impl IPage for StatsPage {
+    fn render(&mut self, frame: &mut Frame, main_area: Rect, config: &Config) {
+        let [title_area, tabs_area, middle_area, help_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(2),
+        ])
+        .areas(main_area.inner(Margin::new(1, 0)));
+
+        let page_title = Line::from("\u{1F4CA} Stats").bold().centered();
+        frame.render_widget(page_title, title_area);
+
+        let tabs = Tabs::new(StatsTab::to_vec_str())
+            .select(self.current_tab)
+            .highlight_style(Style::default().black().on_light_red())
+            .divider(symbols::DOT);
+        frame.render_widget(tabs, tabs_area);
+
+        match self.stats {
+            Err(ref e) => {
+                let error = Paragraph::new(e.as_str())
+                    .light_red()
+                    .wrap(Wrap { trim: true });
+                frame.render_widget(error, middle_area);
+            }
+            Ok(ref stats) if stats.sessions_count == 0 => {
+                let empty = Paragraph::new("No session yet, go study!")
+                    .dim()
+                    .centered()
+                    .wrap(Wrap { trim: true });
+                frame.render_widget(empty, middle_area);
+            }
+            Ok(ref stats) => {
+                let representation = KanaRepresentation::from(config.writing_system.clone());
+                match StatsTab::VARIANTS[self.current_tab] {
+                    StatsTab::Overview => {
+                        render_overview(frame, middle_area, stats, &representation)
+                    }
+                    StatsTab::Kanas => render_kanas(
+                        frame,
+                        middle_area,
+                        stats,
+                        &representation,
+                        &mut self.kanas_offset,
+                    ),
+                    StatsTab::Trend => render_trend(frame, middle_area, stats),
+                }
+            }
+        }
+
+        let help = match StatsTab::VARIANTS[self.current_tab] {
+            StatsTab::Kanas => "up/down to scroll",
+            _ => "left/right to change tab",
+        };
+        frame.render_widget(
+            Paragraph::new(Vec::from([Line::from(help), Line::from("esc to go back")]))
+                .dim()
+                .centered(),
+            help_area,
+        );
+    }
+
+    fn handle_key_events(&mut self, key_event: KeyEvent, _: &mut Config) -> PageEvent {
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                return PageEvent::Navigate(Homepage::default().into());
+            }
+            KeyCode::Right | KeyCode::Tab => {
+                self.current_tab = (self.current_tab + 1) % StatsTab::COUNT;
+            }
+            KeyCode::Left => {
+                self.current_tab = (self.current_tab + StatsTab::COUNT - 1) % StatsTab::COUNT;
+            }
+            KeyCode::Up => self.kanas_offset = self.kanas_offset.saturating_sub(1),
+            KeyCode::Down => self.kanas_offset += 1, // clamped when rendering
+            _ => {}
+        }
+
+        PageEvent::Nothing
+    }
+}
+
+fn render_overview(
+    frame: &mut Frame,
+    area: Rect,
+    stats: &Stats,
+    representation: &KanaRepresentation,
+) {
+    let [summary_area, sparkline_area] =
+        Layout::vertical([Constraint::Fill(1), Constraint::Length(3)]).areas(area);
+
+    let average_seconds = stats.average_elapsed_ms / 1000;
+    let mut lines = Vec::from([
+        Line::from(vec![
+            "Sessions: ".into(),
+            stats.sessions_count.to_string().bold(),
+        ]),
+        Line::from(vec![
+            "Accuracy: ".into(),
+            format!("{:.0}%", stats.accuracy()).bold(),
+        ]),
+        Line::from(vec![
+            "Average time: ".into(),
+            format!("{:02}:{:02}", average_seconds / 60, average_seconds % 60).bold(),
+        ]),
+        Line::from(""),
+        Line::from("Most missed:"),
+    ]);
+    let most_missed = stats.most_missed(5);
+    if most_missed.is_empty() {
+        lines.push(Line::from("nothing, well done!").dim());
+    }
+    for kana_stats in most_missed {
+        lines.push(Line::from(vec![
+            Span::from(format!(
+                "{} ",
+                kana_stats.kana.to_representation(representation)
+            ))
+            .bold(),
+            Span::from(format!("{:<4}", kana_stats.kana)),
+            Span::from(format!(
+                "\u{2717} {}/{}",
+                kana_stats.wrong_count(),
+                kana_stats.total_count
+            ))
+            .light_red(),
+        ]));
+    }
+    frame.render_widget(Paragraph::new(lines), summary_area);
+
+    // only the last sessions fit in the sparkline
+    let accuracies: Vec<u64> = stats
+        .accuracy_per_session
+        .iter()
+        .rev()
+        .take(usize::from(sparkline_area.width))
+        .rev()
+        .map(|accuracy| accuracy.round() as u64)
+        .collect();
+    let sparkline = Sparkline::default()
+        .data(&accuracies)
+        .max(100)
+        .style(Style::default().fg(Color::LightRed));
+    frame.render_widget(sparkline, sparkline_area);
+}
+
+fn render_kanas(
+    frame: &mut Frame,
+    area: Rect,
+    stats: &Stats,
+    representation: &KanaRepresentation,
+    kanas_offset: &mut usize,
+) {
+    const BAR_WIDTH: u16 = 3;
+    const BAR_GAP: u16 = 1;
+
+    let kanas = stats.kanas_by_accuracy();
+    let visible_count = usize::from(area.width.div_ceil(BAR_WIDTH + BAR_GAP));
+    *kanas_offset = (*kanas_offset).min(kanas.len().saturating_sub(visible_count));
+
+    let bars: Vec<Bar> = kanas
+        .iter()
+        .skip(*kanas_offset)
+        .take(visible_count)
+        .map(|kana_stats| {
+            let accuracy = kana_stats.accuracy().round() as u64;
+            Bar::default()
+                .value(accuracy)
+                .text_value(accuracy.to_string())
+                .label(Line::from(
+                    kana_stats.kana.to_representation(representation),
+                ))
+                .style(if accuracy < 50 {
+                    Style::default().fg(Color::LightRed)
+                } else {
+                    Style::default().fg(Color::Gray)
+                })
+                .value_style(Style::default().black().on_gray())
+        })
+        .collect();
+    let bar_chart = BarChart::default()
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(BAR_WIDTH)
+        .bar_gap(BAR_GAP)
+        .max(100);
+    frame.render_widget(bar_chart, area);
+}
+
+fn render_trend(frame: &mut Frame, area: Rect, stats: &Stats) {
+    let points: Vec<(f64, f64)> = stats
+        .accuracy_per_session
+        .iter()
+        .enumerate()
+        .map(|(i, accuracy)| ((i + 1) as f64, *accuracy))
+        .collect();
+    let sessions_count = points.len().max(2) as f64;
+
+    let dataset = Dataset::default()
+        .marker(symbols::Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(Color::LightRed))
+        .data(&points);
+    let chart = Chart::new(Vec::from([dataset]))
+        .x_axis(
+            Axis::default()
+                .title("sessions")
+                .bounds([1_f64, sessions_count])
+                .style(Style::default().dim()),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([0_f64, 100_f64])
+                .labels(["0%", "50%", "100%"])
+                .style(Style::default().dim()),
+        );
+    frame.render_widget(chart, area);
+}
+
+#[derive(Debug, Clone, EnumCount, VariantArray, IntoStaticStr)]
+enum StatsTab {
+    Overview,
+    Kanas,
+    Trend,
+}
+
+impl StatsTab {
+    fn to_vec_str<'a>() -> Vec<&'a str> {
+        Vec::from(StatsTab::VARIANTS)
+            .into_iter()
+            .map(StatsTab::into)
+            .collect()
+    }
+}
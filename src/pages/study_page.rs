@@ -1,113 +1,135 @@
 use super::{Homepage, ResultPage};
 use crate::{
-    app::{IPage, PageEvent},
-    config::Config,
+    app::{self, IPage, PageEvent},
+    config::{AnswerMode, Config, StudyDirection, WritingSystem, CHOICES_COUNT_RANGE},
     models::{
-        answer::{AnswerResult, ValidateAnswer},
-        kana::{Kana, KanaRepresentation},
+        answer::{Answer, AnswerResult, ValidateAnswer},
+        confusable::pick_distractors,
+        deck::{pick_card_distractors, Card, CardAnswer, Deck},
+        history::{History, SessionRecord},
+        kana::Kana,
+        kana_group::KanaRow,
+        kana_input::romaji_to_kana,
+        session::{SavedSession, SessionSettings},
+        srs::ReviewSchedule,
+        study_queue::{missed_items, StudyQueue},
+        word::{kana_answers, pick_word_distractors, Word, WordAnswer},
+        StudyItem, StudyPlan,
     },
     tui,
+    widgets::{BigKana, Choices, ChoicesState},
 };
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent};
+use rand::seq::SliceRandom;
 use ratatui::{
     layout::{Constraint, Flex, Layout, Margin, Rect},
     style::Stylize,
-    text::{Line, ToLine},
+    text::{Line, Span, Text},
+    widgets::{Paragraph, Wrap},
     Frame,
 };
 use std::time::Instant;
+use strum::IntoEnumIterator;
 use tui_popup::Popup;
 use tui_prompts::{Prompt, State, TextPrompt, TextState};
 
+/// Below this height, the page is shown on a single line.
+const MIN_HEIGHT: u16 = 12;
+/// The big kana grows with the page, up to this many times its size.
+const MAX_BIG_KANA_SCALE: u16 = 3;
+
 #[derive(Debug, Clone)]
 pub struct StudyPage {
-    pub kanas: Vec<Kana>,
-    total_kanas: usize,
-    pub answers: Vec<(Kana, AnswerResult)>,
-    current_kana: Kana,
+    /// Kanas (or cards, or words) left to study, and the current one.
+    pub queue: StudyQueue,
+    pub answers: Vec<Answer>,
+    /// Answers of the cards, when studying a deck.
+    pub card_answers: Vec<CardAnswer>,
+    /// Answers of the words, when studying words.
+    pub word_answers: Vec<WordAnswer>,
     indication: Option<Indication>,
     user_input: TextState<'static>,
+    /// Every item of the study, used to pick the choices.
+    choice_pool: Vec<StudyItem>,
+    /// Items to choose from with [`AnswerMode::Choices`], one of them is the current item.
+    /// Empty until the current item is rendered, as they depend on the config.
+    choices: Vec<StudyItem>,
+    choices_state: ChoicesState,
     is_paused: bool,
+    /// Paused because the terminal lost the focus, resumed when it gets it back.
+    is_paused_by_focus: bool,
+    /// Left while another session is saved, the user is asked whether to replace it.
+    leaving: Option<Leaving>,
     /// Timer should eventually be in a widget
     /// Contains our current timer. Is set to None, when the page is paused.
     current_timer: Option<Instant>,
     /// Is updated when our page is paused.
     memory_elapsed_ms: u128,
+    /// Elapsed time when the current kana was shown, to know the response time.
+    current_shown_ms: u128,
+    /// Settings of the session, see [`Self::session_config`].
+    settings: Option<SessionSettings>,
 }
 
 impl IPage for StudyPage {
     fn render(&mut self, frame: &mut Frame, main_area: Rect, config: &Config) {
-        let [timer_area, kana_area, indication_area, input_area, progress_area] =
-            Layout::vertical([
-                Constraint::Length(3),
-                Constraint::Fill(2),
-                Constraint::Length(3),
-                Constraint::Fill(3),
-                Constraint::Length(1),
-            ])
-            .areas(main_area);
-
-        let timer = Line::from(self.format_timer()).dim().centered();
-        frame.render_widget(timer, timer_area.inner(Margin::new(0, 1)));
-
-        let mut kana_title = Line::from(
-            match KanaRepresentation::from(config.writing_system.clone()) {
-                KanaRepresentation::Hiragana => self.current_kana.to_hiragana(),
-                KanaRepresentation::Katakana => self.current_kana.to_katakana(),
-            },
-        )
-        .centered();
-        if config.study_bold_kana {
-            kana_title = kana_title.bold();
+        let config = &self.session_config(config);
+        if self.choices.is_empty() {
+            self.choices = self.create_choices(config);
         }
-        frame.render_widget(
-            kana_title,
-            tui::flex(
-                kana_area,
-                (Flex::Center, Constraint::Length(6)),
-                (Flex::End, Constraint::Length(1)),
-            ),
-        );
 
-        if let Some(ref indication) = self.indication {
-            let good_wrong_indication = indication.to_line().dim().centered();
-            frame.render_widget(
-                good_wrong_indication,
-                tui::flex(
-                    indication_area,
-                    (Flex::Center, Constraint::Fill(1)),
-                    (Flex::Center, Constraint::Length(1)),
-                ),
-            );
+        if main_area.height < MIN_HEIGHT {
+            self.render_compact(frame, main_area, config);
+        } else {
+            self.render_full(frame, main_area, config);
         }
 
-        let user_input = TextPrompt::from("rōmaji");
-        let user_input_layout = tui::flex(
-            input_area,
-            (Flex::Center, Constraint::Length(20)),
-            (Flex::Start, Constraint::Length(1)),
-        );
-        user_input.draw(frame, user_input_layout, &mut self.user_input);
-
-        let progress = Line::from(format!(
-            "{}/{} | \u{2714} {} | \u{2717} {}",
-            self.answers.len(),
-            self.total_kanas,
-            self.get_count_by_result(&AnswerResult::Good),
-            self.get_count_by_result(&AnswerResult::Wrong),
-        ))
-        .dim()
-        .centered();
-        frame.render_widget(progress, progress_area);
-
         if self.is_paused {
             let popup = Popup::new("Press any key to exit.").title("paused");
             frame.render_widget(&popup, frame.area());
+        } else if self.leaving.is_some() {
+            let popup = Popup::new(Text::from(Vec::from([
+                Line::from("Another session is saved."),
+                Line::from("y to replace it"),
+                Line::from("n to leave without saving"),
+                Line::from("esc to continue"),
+            ])))
+            .title("replace?");
+            frame.render_widget(&popup, frame.area());
         }
     }
 
-    fn handle_key_events(&mut self, key_event: KeyEvent, _: &mut Config) -> PageEvent {
+    fn handle_key_events(&mut self, key_event: KeyEvent, config: &mut Config) -> PageEvent {
+        let config = &self.session_config(config);
+        if let Some(leaving) = self.leaving {
+            let event = match leaving {
+                Leaving::Homepage => PageEvent::Navigate(Homepage::default().into()),
+                Leaving::App => PageEvent::QuitApp,
+            };
+            return match key_event.code {
+                KeyCode::Char('y') => {
+                    self.save_session();
+                    event
+                }
+                KeyCode::Char('n') => event,
+                _ => {
+                    self.leaving = None;
+                    if !self.is_paused {
+                        self.reset_timer();
+                    }
+                    PageEvent::Nothing
+                }
+            };
+        }
+
         if !self.is_paused && key_event.code == KeyCode::Esc {
+            // a resumed session is removed when it is loaded, so this is another one
+            if SavedSession::exists() {
+                self.leaving = Some(Leaving::Homepage);
+                self.reset_timer();
+                return PageEvent::Nothing;
+            }
+            self.save_session();
             return PageEvent::Navigate(Homepage::default().into());
         }
 
@@ -117,91 +139,903 @@ impl IPage for StudyPage {
                 && key_event.code == KeyCode::Char('p'))
         {
             self.is_paused = !self.is_paused;
+            self.is_paused_by_focus = false;
             self.reset_timer();
             // early return to prevent all other events
             return PageEvent::Nothing;
         }
 
+        // the meaning of the answered word is shown until the user moves on
+        if self.is_word_answered() {
+            return match key_event.code {
+                KeyCode::Enter | KeyCode::Char(' ') => self.move_on(config),
+                _ => PageEvent::Nothing,
+            };
+        }
+
+        let is_choosing = config.answer_mode == AnswerMode::Choices;
+        let choices_count = self.choices.len();
+        let choices_columns = choices_columns(choices_count);
+
         // handle keyboard events
         match (key_event.modifiers, key_event.code) {
-            (_, KeyCode::Enter) => {
-                if self.is_input_valid() {
-                    self.push_good_answer();
-                    if !self.next_kana() {
-                        self.finish_study_hook();
-                        return PageEvent::Navigate(ResultPage::from(self.clone()).into());
-                    }
-                } else {
-                    self.indication = Some(Indication::WrongAnswer);
-                    self.push_wrong_answer();
-                    self.user_input.truncate();
+            (_, KeyCode::Enter) => return self.answer(config),
+            (_, KeyCode::Char(c @ '1'..='9')) if is_choosing => {
+                let choice = c as usize - '1' as usize;
+                if choice < choices_count {
+                    self.choices_state.current_choice = choice;
+                    return self.answer(config);
                 }
             }
+            (_, KeyCode::Right) if is_choosing => self.choices_state.next_choice(choices_count),
+            (_, KeyCode::Left) if is_choosing => self.choices_state.previous_choice(choices_count),
+            (_, KeyCode::Down) if is_choosing => {
+                self.choices_state
+                    .choice_below(choices_count, choices_columns);
+            }
+            (_, KeyCode::Up) if is_choosing => self.choices_state.choice_above(choices_columns),
             (_, KeyCode::Char(' ')) => {
-                let help = Some(Indication::Help(self.current_kana.clone()));
+                let help = Some(Indication::Help(self.queue.current.clone()));
                 if self.indication.eq(&help) {
-                    if !self.next_kana() {
-                        self.finish_study_hook();
-                        return PageEvent::Navigate(ResultPage::from(self.clone()).into());
-                    }
+                    return self.move_on(config);
                 } else {
                     self.indication = help;
-                    self.push_wrong_answer();
+                    self.push_help(config);
                 }
             }
-            _ => self.user_input.handle_key_event(key_event),
+            _ if !is_choosing => self.user_input.handle_key_event(key_event),
+            _ => {}
         };
 
         PageEvent::Nothing
     }
+
+    /// A click on a choice answers with it, a click anywhere resumes a paused study.
+    fn handle_mouse_events(&mut self, mouse_event: MouseEvent, config: &mut Config) -> PageEvent {
+        let config = &self.session_config(config);
+        let Some(position) = app::left_click(&mouse_event) else {
+            return PageEvent::Nothing;
+        };
+        if self.leaving.is_some() {
+            return PageEvent::Nothing;
+        }
+        if self.is_paused {
+            self.is_paused = false;
+            self.is_paused_by_focus = false;
+            self.reset_timer();
+            return PageEvent::Nothing;
+        }
+        if self.is_word_answered() {
+            return self.move_on(config);
+        }
+        if config.answer_mode != AnswerMode::Choices {
+            return PageEvent::Nothing;
+        }
+        match self.choices_state.choice_at(position) {
+            Some(choice) if choice < self.choices.len() => {
+                self.choices_state.current_choice = choice;
+                self.answer(config)
+            }
+            _ => PageEvent::Nothing,
+        }
+    }
+
+    /// See [`Config::pause_on_focus_lost`], a pause asked by the user is kept.
+    fn handle_focus_change(&mut self, is_focused: bool, config: &Config) {
+        let should_toggle = if is_focused {
+            self.is_paused_by_focus
+        } else {
+            config.pause_on_focus_lost && !self.is_paused && self.leaving.is_none()
+        };
+        if should_toggle {
+            self.is_paused = !is_focused;
+            self.is_paused_by_focus = !is_focused;
+            self.reset_timer();
+        }
+    }
+
+    /// See [`Self::render_compact`].
+    fn has_compact_layout(&self) -> bool {
+        true
+    }
+
+    fn render_side_panels(&mut self, frame: &mut Frame, areas: [Rect; 2], _: &Config) {
+        let [stats_area, chart_area] = areas;
+        let stats_area = tui::render_panel_block(frame, stats_area, "stats");
+        frame.render_widget(
+            Paragraph::new(self.stats_lines()).wrap(Wrap { trim: true }),
+            stats_area.inner(Margin::new(1, 0)),
+        );
+
+        // only kanas have a chart
+        if !matches!(self.queue.current, StudyItem::Kana(..)) {
+            return;
+        }
+        let chart_area = tui::render_panel_block(frame, chart_area, "kanas");
+        frame.render_widget(
+            Paragraph::new(self.chart_lines()).centered(),
+            chart_area.inner(Margin::new(1, 0)),
+        );
+    }
+
+    /// The user is asked before replacing another saved session, the app is quit once they
+    /// answered (or quit again, the other session is then kept).
+    fn on_quit(&mut self) -> bool {
+        match self.leaving {
+            Some(Leaving::App) => true,
+            _ if SavedSession::exists() => {
+                if self.leaving.is_none() && self.current_timer.is_some() {
+                    self.reset_timer();
+                }
+                self.leaving = Some(Leaving::App);
+                false
+            }
+            _ => {
+                self.save_session();
+                true
+            }
+        }
+    }
 }
 
 impl StudyPage {
-    fn is_input_valid(&self) -> bool {
-        self.current_kana.validate_answer(self.user_input.value())
+    fn render_full(&mut self, frame: &mut Frame, main_area: Rect, config: &Config) {
+        // the normal kana is shown when the big one doesn't fit
+        let big_title = self.big_title(config).and_then(|big_title| {
+            (1..=MAX_BIG_KANA_SCALE)
+                .rev()
+                .map(|scale| big_title.clone().scaled(scale))
+                .find(|big_title| {
+                    big_title.width() <= main_area.width
+                        && big_title.height() + MIN_HEIGHT - 2 <= main_area.height
+                })
+        });
+        let kana_height = match &big_title {
+            Some(big_title) => Constraint::Length(big_title.height()),
+            None => Constraint::Fill(2),
+        };
+        let [timer_area, kana_area, indication_area, input_area, progress_area] =
+            Layout::vertical([
+                Constraint::Length(3),
+                kana_height,
+                Constraint::Length(3),
+                Constraint::Fill(3),
+                Constraint::Length(1),
+            ])
+            .areas(main_area);
+
+        let timer = Line::from(self.format_timer()).dim().centered();
+        frame.render_widget(timer, timer_area.inner(Margin::new(0, 1)));
+
+        match big_title {
+            Some(big_title) => {
+                let big_title_area = tui::flex(
+                    kana_area,
+                    (Flex::Center, Constraint::Length(big_title.width())),
+                    (Flex::End, Constraint::Length(big_title.height())),
+                );
+                frame.render_widget(big_title, big_title_area);
+            }
+            None => frame.render_widget(
+                self.title_line(config).centered(),
+                tui::flex(
+                    kana_area,
+                    (Flex::Center, Constraint::Fill(1)),
+                    (Flex::End, Constraint::Length(1)),
+                ),
+            ),
+        }
+
+        if let Some(indication) = self.indication_line(config) {
+            frame.render_widget(
+                indication.centered(),
+                tui::flex(
+                    indication_area,
+                    (Flex::Center, Constraint::Fill(1)),
+                    (Flex::Center, Constraint::Length(1)),
+                ),
+            );
+        }
+
+        match (
+            &self.queue.current,
+            &config.study_direction,
+            &config.answer_mode,
+        ) {
+            (_, _, AnswerMode::Choices) => {
+                let labels: Vec<String> = self
+                    .choices
+                    .iter()
+                    .map(|item| self.choice_label(item, config))
+                    .collect();
+                frame.render_stateful_widget(
+                    Choices::new(
+                        labels.iter().map(String::as_str).collect(),
+                        choices_columns(labels.len()),
+                    ),
+                    input_area,
+                    &mut self.choices_state,
+                );
+            }
+            (
+                StudyItem::Kana(_, representation),
+                StudyDirection::RomajiToKana,
+                AnswerMode::Typing,
+            ) => {
+                let [text_input_area, _, converted_area] = Layout::vertical([
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                ])
+                .areas(input_area);
+                let converted = romaji_to_kana(
+                    self.user_input.value(),
+                    representation,
+                    &config.romanization,
+                );
+                self.render_text_input(frame, text_input_area, config);
+                frame.render_widget(Line::from(converted).bold().centered(), converted_area);
+            }
+            (_, _, AnswerMode::Typing) => self.render_text_input(frame, input_area, config),
+        }
+
+        frame.render_widget(self.progress_line().centered(), progress_area);
+    }
+
+    /// Everything on a single line, for tiny terminals: the item, the indication,
+    /// the answer, and the progress when there is room for it.
+    fn render_compact(&mut self, frame: &mut Frame, main_area: Rect, config: &Config) {
+        let mut title = self.title_line(config);
+        if let Some(indication) = self.indication_line(config) {
+            title.push_span(" ");
+            title.spans.extend(indication.spans);
+        }
+        let progress = self.progress_line();
+        let [title_area, input_area, progress_area] = Layout::horizontal([
+            Constraint::Length(title.width() as u16),
+            Constraint::Min(12),
+            Constraint::Length(progress.width() as u16),
+        ])
+        .spacing(1)
+        .areas(tui::flex(
+            main_area,
+            (Flex::Start, Constraint::Fill(1)),
+            (Flex::Center, Constraint::Length(1)),
+        ));
+        frame.render_widget(title, title_area);
+        frame.render_widget(progress, progress_area);
+
+        match (
+            &self.queue.current,
+            &config.study_direction,
+            &config.answer_mode,
+        ) {
+            (_, _, AnswerMode::Choices) => {
+                let mut choices = Line::default();
+                let mut x = input_area.x;
+                self.choices_state.areas.clear();
+                for (i, item) in self.choices.iter().enumerate() {
+                    let label =
+                        Span::from(format!("{} {}", i + 1, self.choice_label(item, config)));
+                    let width = label.width() as u16;
+                    self.choices_state
+                        .areas
+                        .push(Rect::new(x, input_area.y, width, 1).intersection(input_area));
+                    x = x.saturating_add(width + 2);
+                    choices.push_span(if i == self.choices_state.current_choice {
+                        label.bold().underlined()
+                    } else {
+                        label
+                    });
+                    choices.push_span("  ");
+                }
+                frame.render_widget(choices, input_area);
+            }
+            (
+                StudyItem::Kana(_, representation),
+                StudyDirection::RomajiToKana,
+                AnswerMode::Typing,
+            ) => {
+                let converted = Line::from(romaji_to_kana(
+                    self.user_input.value(),
+                    representation,
+                    &config.romanization,
+                ))
+                .bold();
+                let [text_input_area, converted_area] = Layout::horizontal([
+                    Constraint::Fill(1),
+                    Constraint::Length(converted.width() as u16),
+                ])
+                .areas(input_area);
+                TextPrompt::from("kana").draw(frame, text_input_area, &mut self.user_input);
+                frame.render_widget(converted, converted_area);
+            }
+            (_, _, AnswerMode::Typing) => {
+                TextPrompt::from(self.input_label(config)).draw(
+                    frame,
+                    input_area,
+                    &mut self.user_input,
+                );
+            }
+        }
+    }
+
+    /// The item to answer, with what is expected when it can't be guessed.
+    fn title_line(&self, config: &Config) -> Line<'_> {
+        let mut kana_title = match (&self.queue.current, &config.study_direction) {
+            (StudyItem::Kana(kana, representation), StudyDirection::KanaToRomaji) => {
+                Span::from(kana.to_representation(representation))
+            }
+            (StudyItem::Kana(kana, _), StudyDirection::RomajiToKana) => {
+                Span::from(kana.romaji_in(&config.romanization))
+            }
+            (StudyItem::Card(card), _) => Span::from(card.display.clone()),
+            (StudyItem::Word(word), _) => Span::from(word.to_string()),
+        };
+        if config.study_bold_kana {
+            kana_title = kana_title.bold();
+        }
+        let mut kana_title = Line::from(kana_title);
+        match &self.queue.current {
+            // the script to answer with can't be guessed from the rōmaji
+            StudyItem::Kana(_, representation)
+                if config.study_direction == StudyDirection::RomajiToKana
+                    && config.writing_system == WritingSystem::Both =>
+            {
+                kana_title.push_span(Span::from(format!(" ({})", representation)).dim());
+            }
+            StudyItem::Card(card) if !card.tags.is_empty() => {
+                kana_title.push_span(Span::from(format!(" ({})", card.tags.join(", "))).dim());
+            }
+            _ => {}
+        }
+        kana_title
     }
 
-    /// Update [PageData] with next kana.
-    /// If there are no kana left, return `false` and go to result page.
-    fn next_kana(&mut self) -> bool {
-        if let Some(next_kana) = self.kanas.pop() {
-            self.current_kana = next_kana;
+    /// The kana (or word) drawn with big blocks, see [`Config::study_big_kana`].
+    fn big_title(&self, config: &Config) -> Option<BigKana> {
+        if !config.study_big_kana {
+            return None;
+        }
+        match (&self.queue.current, &config.study_direction) {
+            (StudyItem::Kana(kana, representation), StudyDirection::KanaToRomaji) => {
+                BigKana::new(kana.to_representation(representation))
+            }
+            (StudyItem::Word(word), _) => BigKana::new(&word.to_string()),
+            _ => None,
+        }
+    }
+
+    fn indication_line(&self, config: &Config) -> Option<Line<'_>> {
+        let indication = self.indication.as_ref()?;
+        let line = match (indication, &config.study_direction) {
+            (
+                Indication::Help(StudyItem::Kana(kana, representation)),
+                StudyDirection::RomajiToKana,
+            ) => Line::from(kana.to_representation(representation)),
+            (Indication::Help(StudyItem::Kana(kana, _)), StudyDirection::KanaToRomaji) => {
+                Line::from(kana.romaji_in(&config.romanization))
+            }
+            (Indication::Help(StudyItem::Word(word)), _) => Line::from(format!(
+                "{} ({})",
+                word.romaji_in(&config.romanization),
+                word.meaning
+            )),
+            // the meaning doesn't give the reading away
+            (Indication::WrongAnswer, _) => match &self.queue.current {
+                StudyItem::Word(word) => Line::from(format!("{} {}", indication, word.meaning)),
+                _ => Line::from(indication.to_string()),
+            },
+            (indication, _) => Line::from(indication.to_string()),
+        };
+        Some(line.dim())
+    }
+
+    fn progress_line(&self) -> Line<'static> {
+        let progress = if self.queue.is_retry_round {
+            format!("retry | {} left", self.queue.left())
+        } else {
+            format!(
+                "{}/{}",
+                self.answers.len() + self.card_answers.len() + self.word_answers.len(),
+                self.queue.total_items
+            )
+        };
+        Line::from(format!(
+            "{} | \u{2714} {} | \u{2717} {}",
+            progress,
+            self.get_count_by_result(&AnswerResult::Good),
+            self.get_count_by_result(&AnswerResult::Wrong),
+        ))
+        .dim()
+    }
+
+    /// Live stats of the session, with the missed items.
+    fn stats_lines(&self) -> Vec<Line<'_>> {
+        let good_count = self.get_count_by_result(&AnswerResult::Good);
+        let wrong_count = self.get_count_by_result(&AnswerResult::Wrong);
+        let answered_count = good_count + wrong_count;
+        let response_times: Vec<u64> = self
+            .answers
+            .iter()
+            .filter(|answer| !answer.help_used)
+            .map(|answer| answer.response_ms)
+            .chain(
+                self.card_answers
+                    .iter()
+                    .filter(|answer| !answer.help_used)
+                    .map(|answer| answer.response_ms),
+            )
+            .chain(
+                self.word_answers
+                    .iter()
+                    .filter(|answer| !answer.help_used)
+                    .map(|answer| answer.response_ms),
+            )
+            .collect();
+
+        let stat_line = |label: &'static str, value: String| {
+            Line::from(Vec::from([
+                Span::from(format!("{:<10}", label)).dim(),
+                Span::from(value),
+            ]))
+        };
+        let mut lines = Vec::from([
+            stat_line("good", good_count.to_string()).green(),
+            stat_line("wrong", wrong_count.to_string()).light_red(),
+            stat_line(
+                "accuracy",
+                match answered_count {
+                    0 => String::from("-"),
+                    count => format!("{}%", good_count * 100 / count),
+                },
+            ),
+            stat_line(
+                "avg. time",
+                match response_times.len() {
+                    0 => String::from("-"),
+                    count => format!(
+                        "{:.1}s",
+                        response_times.iter().sum::<u64>() as f64 / count as f64 / 1000.0
+                    ),
+                },
+            ),
+            stat_line("left", self.queue.left().to_string()),
+        ]);
+
+        let missed: Vec<String> = self
+            .answers
+            .iter()
+            .filter(|answer| answer.result == AnswerResult::Wrong)
+            .map(|answer| {
+                answer
+                    .kana
+                    .to_representation(&answer.representation)
+                    .to_string()
+            })
+            .chain(
+                self.card_answers
+                    .iter()
+                    .filter(|answer| answer.result == AnswerResult::Wrong)
+                    .map(|answer| answer.card.display.clone()),
+            )
+            .chain(
+                self.word_answers
+                    .iter()
+                    .filter(|answer| answer.result == AnswerResult::Wrong)
+                    .map(|answer| answer.word.to_string()),
+            )
+            .collect();
+        if !missed.is_empty() {
+            lines.push(Line::default());
+            lines.push(Line::from("missed").dim());
+            lines.push(Line::from(missed.join(" ")));
+        }
+        lines
+    }
+
+    /// The kana table, filled with the answered kanas (green when good, red when wrong).
+    /// The kanas left are hidden, their place in the table would give the answer away.
+    fn chart_lines(&self) -> Vec<Line<'_>> {
+        let studied: Vec<&Kana> = self
+            .choice_pool
+            .iter()
+            .filter_map(|item| match item {
+                StudyItem::Kana(kana, _) => Some(kana),
+                _ => None,
+            })
+            .collect();
+
+        KanaRow::iter()
+            .filter(|row| row.kanas().iter().any(|kana| studied.contains(&kana)))
+            .map(|row| {
+                let spans: Vec<Span> = row
+                    .kanas()
+                    .iter()
+                    .map(|kana| {
+                        let mut answers = self.answers.iter().filter(|answer| answer.kana.eq(kana));
+                        match answers.next_back() {
+                            Some(answer) => {
+                                let glyph =
+                                    Span::from(kana.to_representation(&answer.representation));
+                                if answers.any(|other| other.result == AnswerResult::Wrong)
+                                    || answer.result == AnswerResult::Wrong
+                                {
+                                    glyph.light_red()
+                                } else {
+                                    glyph.green()
+                                }
+                            }
+                            None if studied.contains(&kana) => Span::from("\u{30FB}").dark_gray(),
+                            None => Span::from("  "),
+                        }
+                    })
+                    .flat_map(|glyph| [glyph, Span::from(" ")])
+                    .collect();
+                Line::from(spans)
+            })
+            .collect()
+    }
+
+    fn input_label(&self, config: &Config) -> &'static str {
+        match (&self.queue.current, &config.study_direction) {
+            (StudyItem::Card(_), _) => "answer",
+            (StudyItem::Kana(..), StudyDirection::RomajiToKana) => "kana",
+            _ => "rōmaji",
+        }
+    }
+
+    fn is_answer_valid(&self, config: &Config) -> bool {
+        match (
+            &self.queue.current,
+            &config.study_direction,
+            &config.answer_mode,
+        ) {
+            (_, _, AnswerMode::Choices) => self
+                .choices
+                .get(self.choices_state.current_choice)
+                .is_some_and(|item| item.eq(&self.queue.current)),
+            (StudyItem::Card(card), _, AnswerMode::Typing) => {
+                card.validate_answer(self.user_input.value())
+            }
+            (StudyItem::Word(word), _, AnswerMode::Typing) => {
+                word.validate_answer_in(self.user_input.value(), &config.romanization)
+            }
+            (StudyItem::Kana(kana, _), StudyDirection::KanaToRomaji, AnswerMode::Typing) => {
+                kana.validate_answer_in(self.user_input.value(), &config.romanization)
+            }
+            (
+                StudyItem::Kana(kana, representation),
+                StudyDirection::RomajiToKana,
+                AnswerMode::Typing,
+            ) => kana.validate_typed_kana(
+                self.user_input.value(),
+                representation,
+                &config.romanization,
+            ),
+        }
+    }
+
+    /// Validate the current answer, and go to the next item if it is good.
+    /// The meaning of a word is shown first, see [`Self::is_word_answered`].
+    fn answer(&mut self, config: &Config) -> PageEvent {
+        let input = self.current_input(config);
+        if !self.is_answer_valid(config) {
+            self.indication = Some(Indication::WrongAnswer);
+            self.push_wrong_answer(input, config);
+            self.user_input.truncate();
+            return PageEvent::Nothing;
+        }
+
+        self.push_good_answer(input, config);
+        match &self.queue.current {
+            StudyItem::Word(word) => {
+                self.indication = Some(Indication::Meaning(word.clone()));
+                PageEvent::Nothing
+            }
+            _ => self.move_on(config),
+        }
+    }
+
+    /// The word was read, its meaning is shown until the user moves on.
+    fn is_word_answered(&self) -> bool {
+        matches!(self.indication, Some(Indication::Meaning(_)))
+    }
+
+    /// Go to the next item, or to the results when there is none left.
+    fn move_on(&mut self, config: &Config) -> PageEvent {
+        if !self.next_item(config) {
+            return self.finish_study(config);
+        }
+        PageEvent::Nothing
+    }
+
+    /// What was typed, or the label of the picked choice.
+    fn current_input(&self, config: &Config) -> String {
+        match config.answer_mode {
+            AnswerMode::Typing => self.user_input.value().to_string(),
+            AnswerMode::Choices => self
+                .choices
+                .get(self.choices_state.current_choice)
+                .map(|item| self.choice_label(item, config))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Choices are the rōmaji when studying from kana, or the kana when studying from rōmaji.
+    /// The choices of a card are the first answer of each card, the rōmaji for words.
+    fn choice_label(&self, item: &StudyItem, config: &Config) -> String {
+        match (item, &config.study_direction) {
+            (StudyItem::Kana(kana, _), StudyDirection::KanaToRomaji) => {
+                kana.romaji_in(&config.romanization).to_string()
+            }
+            (StudyItem::Kana(kana, representation), StudyDirection::RomajiToKana) => {
+                kana.to_representation(representation).to_string()
+            }
+            (StudyItem::Card(card), _) => card.first_answer().to_string(),
+            (StudyItem::Word(word), _) => word.romaji_in(&config.romanization),
+        }
+    }
+
+    fn render_text_input(&mut self, frame: &mut Frame, area: Rect, config: &Config) {
+        let user_input = TextPrompt::from(self.input_label(config));
+        let user_input_layout = tui::flex(
+            area,
+            (Flex::Center, Constraint::Length(20)),
+            (Flex::Start, Constraint::Length(1)),
+        );
+        user_input.draw(frame, user_input_layout, &mut self.user_input);
+    }
+
+    /// The current kana, with kanas that are easily confused with it.
+    /// Or the current card (or word), with other cards of the deck.
+    fn create_choices(&self, config: &Config) -> Vec<StudyItem> {
+        let count = config
+            .choices_count
+            .clamp(*CHOICES_COUNT_RANGE.start(), *CHOICES_COUNT_RANGE.end());
+        let mut choices: Vec<StudyItem> = match &self.queue.current {
+            StudyItem::Kana(kana, representation) => {
+                let kana_pool: Vec<Kana> = self
+                    .choice_pool
+                    .iter()
+                    .filter_map(|item| match item {
+                        StudyItem::Kana(kana, _) => Some(kana.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                pick_distractors(
+                    kana,
+                    representation,
+                    &config.romanization,
+                    &kana_pool,
+                    count - 1,
+                )
+                .into_iter()
+                .map(|distractor| StudyItem::Kana(distractor, representation.clone()))
+                .collect()
+            }
+            StudyItem::Card(card) => {
+                let card_pool: Vec<_> = self
+                    .choice_pool
+                    .iter()
+                    .filter_map(|item| match item {
+                        StudyItem::Card(card) => Some(card.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                pick_card_distractors(card, &card_pool, count - 1)
+                    .into_iter()
+                    .map(StudyItem::Card)
+                    .collect()
+            }
+            StudyItem::Word(word) => {
+                let word_pool: Vec<Word> = self
+                    .choice_pool
+                    .iter()
+                    .filter_map(|item| match item {
+                        StudyItem::Word(word) => Some(word.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                pick_word_distractors(word, &word_pool, count - 1)
+                    .into_iter()
+                    .map(StudyItem::Word)
+                    .collect()
+            }
+        };
+        choices.push(self.queue.current.clone());
+        choices.shuffle(&mut rand::rng());
+        choices
+    }
+
+    /// Update [PageData] with next item.
+    /// If there are no item left, return `false` and go to result page.
+    fn next_item(&mut self, config: &Config) -> bool {
+        let has_next_item = self.queue.next(config.retry_missed, || {
+            missed_items(&self.answers, &self.card_answers, &self.word_answers)
+        });
+        if has_next_item {
+            self.current_shown_ms = self.total_elapsed_time_ms();
             self.indication = None;
             self.user_input = TextState::new().with_focus(tui_prompts::FocusState::Focused);
-            return true;
+            self.choices.clear();
+            self.choices_state = ChoicesState::default();
         }
+        has_next_item
+    }
 
-        false
+    fn push_good_answer(&mut self, input: String, config: &Config) {
+        self.push_answer(AnswerResult::Good, Some(input), config);
     }
 
-    fn push_good_answer(&mut self) {
-        if self.answers.iter().any(|(g, _)| g.eq(&self.current_kana)) {
-            return;
-        }
-        self.answers
-            .push((self.current_kana.clone(), AnswerResult::Good));
+    fn push_wrong_answer(&mut self, input: String, config: &Config) {
+        self.queue.is_current_missed = true;
+        self.push_answer(AnswerResult::Wrong, Some(input), config);
     }
 
-    fn push_wrong_answer(&mut self) {
-        if self.answers.iter().any(|(g, _)| g.eq(&self.current_kana)) {
-            return;
+    fn push_help(&mut self, config: &Config) {
+        self.queue.is_current_missed = true;
+        self.push_answer(AnswerResult::Wrong, None, config);
+    }
+
+    /// Only the first answer of the current item is kept, the next ones are counted as attempts.
+    /// Help is given when there is no input.
+    fn push_answer(&mut self, result: AnswerResult, input: Option<String>, config: &Config) {
+        let is_help = input.is_none();
+        let response_ms = self.total_elapsed_time_ms() - self.current_shown_ms;
+        // the answers of the retry round are only counted as retries
+        let is_counted = !self.queue.is_retry_round;
+        match &self.queue.current {
+            StudyItem::Kana(kana, representation) => {
+                if let Some(answer) = self
+                    .answers
+                    .iter_mut()
+                    .find(|answer| answer.kana.eq(kana) && answer.representation.eq(representation))
+                {
+                    if is_counted {
+                        answer.help_used |= is_help;
+                        answer.attempts += u32::from(!is_help);
+                    }
+                    return;
+                }
+
+                let mut answer = Answer::new(kana.clone(), result, representation.clone())
+                    .with_input(input.unwrap_or_default())
+                    .with_response_ms(response_ms);
+                answer.help_used = is_help;
+                answer.attempts = u32::from(!is_help);
+                self.answers.push(answer);
+            }
+            StudyItem::Card(card) => {
+                if let Some(answer) = self
+                    .card_answers
+                    .iter_mut()
+                    .find(|answer| answer.card.eq(card))
+                {
+                    if is_counted {
+                        answer.help_used |= is_help;
+                        answer.attempts += u32::from(!is_help);
+                    }
+                    return;
+                }
+
+                self.card_answers.push(CardAnswer {
+                    card: card.clone(),
+                    result,
+                    input: input.unwrap_or_default(),
+                    attempts: u32::from(!is_help),
+                    help_used: is_help,
+                    response_ms: u64::try_from(response_ms).unwrap_or(u64::MAX),
+                });
+            }
+            StudyItem::Word(word) => {
+                if let Some(answer) = self
+                    .word_answers
+                    .iter_mut()
+                    .find(|answer| answer.word.eq(word))
+                {
+                    if is_counted {
+                        answer.help_used |= is_help;
+                        answer.attempts += u32::from(!is_help);
+                    }
+                    return;
+                }
+
+                let input = input.unwrap_or_default();
+                self.word_answers.push(WordAnswer {
+                    word: word.clone(),
+                    result,
+                    // every kana is missed when help is asked
+                    wrong_kanas: word.wrong_kanas(&input, &config.romanization),
+                    input,
+                    attempts: u32::from(!is_help),
+                    help_used: is_help,
+                    response_ms: u64::try_from(response_ms).unwrap_or(u64::MAX),
+                });
+            }
         }
-        self.answers
-            .push((self.current_kana.clone(), AnswerResult::Wrong));
     }
 
-    fn finish_study_hook(&mut self) {
+    /// Save the session, and show its results.
+    fn finish_study(&mut self, config: &Config) -> PageEvent {
         // only reset timer if we have one
         if self.current_timer.is_some() {
             self.reset_timer();
         }
+
+        let record = SessionRecord::new(
+            config.writing_system.clone(),
+            self.answers.clone(),
+            self.total_elapsed_time_ms(),
+        );
+        // decks are not saved, the history and the review schedule are only about kanas,
+        // words are saved as the answers of their kanas
+        let mut saved_record = record.clone();
+        saved_record
+            .answers
+            .extend(kana_answers(&self.word_answers));
+        if !saved_record.answers.is_empty() {
+            let _ = ReviewSchedule::update_saved(&saved_record.answers);
+            let _ = History::append(saved_record); // should show popup if failed to save?
+        }
+
+        PageEvent::Navigate(
+            ResultPage::new(
+                record,
+                self.card_answers.clone(),
+                self.word_answers.clone(),
+                self.queue.retries.clone(),
+            )
+            .into(),
+        )
     }
 
     pub fn get_count_by_result(&self, result: &AnswerResult) -> usize {
-        self.answers
+        let kana_count = self
+            .answers
+            .iter()
+            .filter(|answer| answer.result.eq(result))
+            .count();
+        let card_count = self
+            .card_answers
             .iter()
-            .filter_map(|(_, r)| r.eq(result).then_some(()))
-            .count()
+            .filter(|answer| answer.result.eq(result))
+            .count();
+        let word_count = self
+            .word_answers
+            .iter()
+            .filter(|answer| answer.result.eq(result))
+            .count();
+        kana_count + card_count + word_count
+    }
+
+    /// Save the session to resume it later, the timer is paused.
+    fn save_session(&mut self) {
+        if self.current_timer.is_some() {
+            self.reset_timer();
+        }
+        let _ = self.to_saved().save(); // should show popup if failed to save?
+    }
+
+    fn to_saved(&self) -> SavedSession {
+        SavedSession {
+            queue: self.queue.clone(),
+            choice_pool: self.choice_pool.clone(),
+            answers: self.answers.clone(),
+            card_answers: self.card_answers.clone(),
+            word_answers: self.word_answers.clone(),
+            elapsed_ms: u64::try_from(self.total_elapsed_time_ms()).unwrap_or(u64::MAX),
+            current_shown_ms: u64::try_from(self.current_shown_ms).unwrap_or(u64::MAX),
+            settings: self.settings.clone(),
+        }
+    }
+
+    /// The config with the settings of the session, they are taken from the config the first
+    /// time so that changing it doesn't change the session (e.g. once it is resumed).
+    fn session_config(&mut self, config: &Config) -> Config {
+        self.settings
+            .get_or_insert_with(|| SessionSettings::from(config))
+            .apply(config)
     }
 
     /// Used when we pause our page, will save our last elapsed time in `self.memory_elapsed_time`
@@ -231,37 +1065,177 @@ impl StudyPage {
     }
 }
 
-impl Default for StudyPage {
-    fn default() -> Self {
-        let mut kanas = crate::models::create_study_plan();
-        let total_kanas = kanas.len();
-        let first_kana = kanas.pop().unwrap(); // panic should not happen
+impl StudyPage {
+    /// Returns `None` if there is nothing to study.
+    pub fn new(kanas: StudyPlan) -> Option<Self> {
+        Self::from_items(kanas.into_iter().map(StudyItem::from).collect())
+    }
+
+    /// Study the cards of the deck in a random order.
+    pub fn from_deck(deck: Deck) -> Option<Self> {
+        let mut cards = deck.cards;
+        cards.shuffle(&mut rand::rng());
+        Self::from_cards(cards)
+    }
+
+    /// Cards are shown as they are, whatever the study direction.
+    pub fn from_cards(cards: Vec<Card>) -> Option<Self> {
+        Self::from_items(cards.into_iter().map(StudyItem::Card).collect())
+    }
+
+    /// Words are always shown in kana, like cards.
+    pub fn from_words(words: Vec<Word>) -> Option<Self> {
+        Self::from_items(words.into_iter().map(StudyItem::Word).collect())
+    }
+
+    /// Continue a saved session where it was left, paused until a key is pressed.
+    pub fn from_saved(session: SavedSession) -> Self {
         Self {
-            kanas,
-            total_kanas,
-            current_kana: first_kana,
+            queue: session.queue,
+            indication: None,
+            answers: session.answers,
+            card_answers: session.card_answers,
+            word_answers: session.word_answers,
+            user_input: TextState::new().with_focus(tui_prompts::FocusState::Focused),
+            choice_pool: session.choice_pool,
+            choices: Vec::new(),
+            choices_state: ChoicesState::default(),
+            is_paused: true,
+            is_paused_by_focus: false,
+            leaving: None,
+            current_timer: None,
+            memory_elapsed_ms: u128::from(session.elapsed_ms),
+            current_shown_ms: u128::from(session.current_shown_ms),
+            settings: session.settings,
+        }
+    }
+
+    /// Study with these settings rather than the ones of the config, without changing it.
+    pub fn with_settings(mut self, settings: SessionSettings) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
+    fn from_items(items: Vec<StudyItem>) -> Option<Self> {
+        let choice_pool = items.clone();
+        Some(Self {
+            queue: StudyQueue::new(items)?,
             indication: None,
             answers: Vec::new(),
+            card_answers: Vec::new(),
+            word_answers: Vec::new(),
             user_input: TextState::new().with_focus(tui_prompts::FocusState::Focused),
+            choice_pool,
+            choices: Vec::new(),
+            choices_state: ChoicesState::default(),
             is_paused: false,
+            is_paused_by_focus: false,
+            leaving: None,
             // start immediately
             current_timer: Some(Instant::now()),
             memory_elapsed_ms: 0,
-        }
+            current_shown_ms: 0,
+            settings: None,
+        })
     }
 }
 
+/// Where the user goes once they chose whether to replace the saved session.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Leaving {
+    Homepage,
+    App,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 enum Indication {
     WrongAnswer,
-    Help(Kana),
+    Help(StudyItem),
+    /// Shown after the good answer of a word, the meaning is also shown with a wrong answer.
+    Meaning(Word),
 }
 
 impl std::fmt::Display for Indication {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::WrongAnswer => write!(f, "\u{274C}"),
-            Self::Help(kana) => write!(f, "{}", kana),
+            Self::Help(StudyItem::Kana(kana, _)) => write!(f, "{}", kana),
+            Self::Help(StudyItem::Card(card)) => match &card.notes {
+                Some(notes) => write!(f, "{} ({})", card.answers.join(", "), notes),
+                None => write!(f, "{}", card.answers.join(", ")),
+            },
+            Self::Help(StudyItem::Word(word)) => write!(f, "{} ({})", word.romaji(), word.meaning),
+            Self::Meaning(word) => write!(f, "\u{2714} {}: {}", word, word.meaning),
         }
     }
 }
+
+/// Short rōmaji and kanas fit on 3 columns, 2 columns look better with few choices.
+fn choices_columns(count: usize) -> usize {
+    if count <= 4 {
+        2
+    } else {
+        3
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::{Romanization, StudyDirection},
+        models::kana::KanaRepresentation,
+    };
+
+    #[test]
+    fn save_and_resume_session() {
+        let session_config = Config {
+            writing_system: WritingSystem::Katakana,
+            study_direction: StudyDirection::RomajiToKana,
+            answer_mode: AnswerMode::Choices,
+            romanization: Romanization::Nihon,
+            ..Config::default()
+        };
+        let plan = Vec::from([
+            (Kana::A, KanaRepresentation::Katakana),
+            (Kana::I, KanaRepresentation::Katakana),
+        ]);
+        let mut page = StudyPage::new(plan)
+            .unwrap()
+            .with_settings(SessionSettings::from(&session_config));
+        page.push_wrong_answer(String::from("ア"), &session_config);
+        assert!(page.next_item(&session_config));
+
+        let saved = page.to_saved();
+        let toml = toml::to_string(&saved).unwrap();
+        let mut resumed = StudyPage::from_saved(toml::from_str(&toml).unwrap());
+        assert!(resumed.is_paused);
+        assert_eq!(resumed.to_saved(), saved);
+        assert_eq!(
+            resumed.queue.current,
+            StudyItem::Kana(Kana::A, KanaRepresentation::Katakana)
+        );
+        assert_eq!(resumed.answers.len(), 1);
+
+        // the settings of the session are kept, whatever the config
+        let config = Config::default();
+        let resumed_config = resumed.session_config(&config);
+        assert_eq!(resumed_config.writing_system, WritingSystem::Katakana);
+        assert_eq!(resumed_config.study_direction, StudyDirection::RomajiToKana);
+        assert_eq!(resumed_config.answer_mode, AnswerMode::Choices);
+        assert_eq!(resumed_config.romanization, Romanization::Nihon);
+
+        // without settings, they are the ones of the config when the session starts
+        let mut page =
+            StudyPage::new(Vec::from([(Kana::A, KanaRepresentation::Hiragana)])).unwrap();
+        assert_eq!(page.session_config(&config).answer_mode, config.answer_mode);
+        assert_eq!(
+            page.session_config(&session_config).answer_mode,
+            config.answer_mode
+        );
+        assert_eq!(
+            page.to_saved().settings,
+            Some(SessionSettings::from(&config))
+        );
+    }
+}
@@ -0,0 +1,422 @@
+//! Transliteration of any text between kana (hiragana and katakana) and Hepburn rōmaji.
+//!
+//! ```
+//! use kana_tui::transliteration::{to_hiragana, to_katakana, to_romaji};
+//!
+//! assert_eq!(to_romaji("がっこう"), "gakkou");
+//! assert_eq!(to_romaji("ラーメン"), "rāmen");
+//! assert_eq!(to_hiragana("kin'en"), "きんえん");
+//! assert_eq!(to_katakana("koohii"), "コオヒイ");
+//! assert_eq!(to_katakana("kōhī"), "コーヒー");
+//! ```
+
+use crate::{
+    config::Romanization,
+    models::{
+        answer::LONG_VOWELS,
+        kana::{Kana, KanaRepresentation},
+    },
+};
+use strum::IntoEnumIterator;
+
+/// Longest rōmaji spelling of a kana (e.g. "tchi").
+const MAX_ROMAJI_LENGTH: usize = 4;
+
+/// Sokuon, the small tsu doubling the next consonant.
+const SMALL_TSU: [&str; 2] = ["っ", "ッ"];
+/// Chōon, the long vowel mark (mostly used in katakana).
+const LONG_VOWEL: &str = "ー";
+
+/// Rōmaji of a small tsu that doesn't double a consonant (e.g. at the end of the text).
+const SMALL_TSU_ROMAJI: &str = "xtsu";
+
+/// Convert the kanas of the text to Hepburn rōmaji, the other characters are kept as they are.
+///
+/// Long vowels written with kanas are kept (とうきょう is "toukyou"), only ー becomes a macron.
+/// ん is written "n'" before a vowel or a y, to tell apart きんえん (kin'en) and きねん (kinen).
+/// As in Hepburn texts, ぢ and づ are "ji" and "zu" like じ and ず, and を is "o".
+pub fn to_romaji(text: &str) -> String {
+    to_romaji_in(text, &Romanization::Hepburn)
+}
+
+/// Same as [`to_romaji`], in the romanization system.
+pub(crate) fn to_romaji_in(text: &str, romanization: &Romanization) -> String {
+    let tokens = tokenize(text);
+    let mut romaji = String::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        let next_spelling = match tokens.get(i + 1) {
+            Some(Token::Kana(kana)) => Some(spelling(kana, romanization)),
+            _ => None,
+        };
+        match token {
+            Token::Kana(Kana::N) => {
+                romaji.push('n');
+                if next_spelling
+                    .is_some_and(|spelling| spelling.starts_with(['a', 'i', 'u', 'e', 'o', 'y']))
+                {
+                    romaji.push('\'');
+                }
+            }
+            Token::Kana(kana) => romaji.push_str(spelling(kana, romanization)),
+            Token::SmallTsu => match next_spelling {
+                // っち is "tchi", or "cchi" where "tchi" would be read as ち
+                Some(spelling)
+                    if spelling.starts_with("ch") && romaji.ends_with(char::is_alphabetic) =>
+                {
+                    romaji.push('t');
+                }
+                Some(spelling) if spelling.starts_with(is_doubled_consonant) => {
+                    romaji.extend(spelling.chars().next());
+                }
+                _ => romaji.push_str(SMALL_TSU_ROMAJI),
+            },
+            Token::LongVowel => match romaji.pop() {
+                Some(vowel) if macron(vowel).is_some() => romaji.extend(macron(vowel)),
+                previous => {
+                    romaji.extend(previous);
+                    romaji.push('-');
+                }
+            },
+            Token::Other(c) => romaji.push(*c),
+        }
+    }
+
+    romaji
+}
+
+/// Convert the rōmaji and the katakana of the text to hiragana, the characters that can't be
+/// converted are kept.
+///
+/// Alternative spellings are accepted (e.g. "sya" for しゃ), long vowels with a macron are
+/// written with a vowel (ō is おう).
+pub fn to_hiragana(text: &str) -> String {
+    let representation = KanaRepresentation::Hiragana;
+    to_kana(&to_script(text, &representation), &representation)
+}
+
+/// Convert the rōmaji and the hiragana of the text to katakana, the characters that can't be
+/// converted are kept.
+///
+/// Alternative spellings are accepted (e.g. "sya" for しゃ), long vowels with a macron
+/// (or a dash) are written with ー.
+pub fn to_katakana(text: &str) -> String {
+    let representation = KanaRepresentation::Katakana;
+    to_kana(&to_script(text, &representation), &representation)
+}
+
+/// Write the kanas of the text in the script, the other characters are kept.
+fn to_script(text: &str, representation: &KanaRepresentation) -> String {
+    tokenize(text)
+        .into_iter()
+        .map(|token| match token {
+            Token::Kana(kana) => kana.to_representation(representation).to_string(),
+            Token::SmallTsu => small_tsu(representation).to_string(),
+            Token::LongVowel => LONG_VOWEL.to_string(),
+            Token::Other(c) => c.to_string(),
+        })
+        .collect()
+}
+
+/// Convert the rōmaji to kana, from left to right with the longest known spelling.
+pub(crate) fn to_kana(romaji: &str, representation: &KanaRepresentation) -> String {
+    to_kana_in(romaji, representation, &Romanization::Hepburn)
+}
+
+/// Same as [`to_kana`], the spellings of the romanization system come first (e.g. "ti" is ち
+/// in Kunrei-shiki, and ティ in Hepburn).
+pub(crate) fn to_kana_in(
+    romaji: &str,
+    representation: &KanaRepresentation,
+    romanization: &Romanization,
+) -> String {
+    let chars = expand_long_vowels(&romaji.to_lowercase(), representation);
+    let small_tsu = small_tsu(representation);
+    let mut converted = String::new();
+    let mut start = 0;
+
+    while start < chars.len() {
+        let rest = &chars[start..];
+        let rest_starts_with = |prefix: &str| {
+            rest.iter()
+                .take(prefix.chars().count())
+                .copied()
+                .eq(prefix.chars())
+        };
+        match rest {
+            ['-', ..] => {
+                converted.push_str(LONG_VOWEL);
+                start += 1;
+                continue;
+            }
+            ['n', '\'', ..] => {
+                converted.push_str(Kana::N.to_representation(representation));
+                start += 2;
+                continue;
+            }
+            // a lone "tchi" is ち, one of its aliases
+            ['t', 'c', 'h', ..] if start > 0 && chars[start - 1].is_alphabetic() => {
+                converted.push_str(small_tsu);
+                start += 1;
+                continue;
+            }
+            [c, next, ..] if c == next && is_doubled_consonant(*c) => {
+                converted.push_str(small_tsu);
+                start += 1;
+                continue;
+            }
+            _ => {}
+        }
+        if let Some(spelling) = ["xtsu", "ltsu", "xtu", "ltu"]
+            .into_iter()
+            .find(|spelling| rest_starts_with(spelling))
+        {
+            converted.push_str(small_tsu);
+            start += spelling.len();
+            continue;
+        }
+
+        let longest_match = (1..=MAX_ROMAJI_LENGTH.min(rest.len()))
+            .rev()
+            .find_map(|length| {
+                let chunk: String = rest[..length].iter().collect();
+                Kana::from_romaji_in(&chunk, romanization).map(|kana| (kana, length))
+            });
+        match longest_match {
+            Some((kana, length)) => {
+                converted.push_str(kana.to_representation(representation));
+                start += length;
+            }
+            None => {
+                // can't be converted (yet), keep it
+                converted.push(chars[start]);
+                start += 1;
+            }
+        }
+    }
+
+    converted
+}
+
+/// The kanas of the text, the other characters (including っ and ー) are `None`.
+pub(crate) fn split_kanas(text: &str) -> Vec<Option<Kana>> {
+    tokenize(text)
+        .into_iter()
+        .map(|token| match token {
+            Token::Kana(kana) => Some(kana),
+            _ => None,
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Kana(Kana),
+    SmallTsu,
+    LongVowel,
+    Other(char),
+}
+
+/// Split the text into kanas, with the longest glyph first (e.g. きょ before き).
+fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let longest_kana = Kana::iter()
+            .flat_map(|kana| {
+                [kana.to_hiragana(), kana.to_katakana()]
+                    .into_iter()
+                    .filter(|glyph| rest.starts_with(glyph))
+                    .map(|glyph| (kana.clone(), glyph.len()))
+                    .collect::<Vec<_>>()
+            })
+            .max_by_key(|(_, length)| *length);
+        let (token, length) = match longest_kana {
+            Some((kana, length)) => (Token::Kana(kana), length),
+            None if SMALL_TSU.iter().any(|glyph| rest.starts_with(glyph)) => {
+                (Token::SmallTsu, c.len_utf8())
+            }
+            None if rest.starts_with(LONG_VOWEL) => (Token::LongVowel, c.len_utf8()),
+            None => (Token::Other(c), c.len_utf8()),
+        };
+        tokens.push(token);
+        rest = &rest[length..];
+    }
+    tokens
+}
+
+fn small_tsu(representation: &KanaRepresentation) -> &'static str {
+    match representation {
+        KanaRepresentation::Hiragana => SMALL_TSU[0],
+        KanaRepresentation::Katakana => SMALL_TSU[1],
+    }
+}
+
+/// The spelling of the kana, Hepburn is written as in texts rather than as in the app.
+fn spelling(kana: &Kana, romanization: &Romanization) -> &'static str {
+    match (romanization, kana) {
+        (Romanization::Hepburn | Romanization::Lenient, Kana::Dji) => "ji",
+        (Romanization::Hepburn | Romanization::Lenient, Kana::Dzu) => "zu",
+        (Romanization::Hepburn | Romanization::Lenient, Kana::Wo) => "o",
+        (romanization, kana) => kana.romaji_in(romanization),
+    }
+}
+
+/// The vowels with a macron are written as the vowel followed by a dash in katakana,
+/// and as a doubled vowel in hiragana (ō as "ou").
+fn expand_long_vowels(romaji: &str, representation: &KanaRepresentation) -> Vec<char> {
+    romaji
+        .chars()
+        .flat_map(
+            |c| match LONG_VOWELS.iter().find(|(long_vowel, _)| *long_vowel == c) {
+                Some((_, vowel)) => match (representation, vowel) {
+                    (KanaRepresentation::Katakana, _) => Vec::from([*vowel, '-']),
+                    (KanaRepresentation::Hiragana, 'o') => Vec::from(['o', 'u']),
+                    (KanaRepresentation::Hiragana, _) => Vec::from([*vowel, *vowel]),
+                },
+                None => Vec::from([c]),
+            },
+        )
+        .collect()
+}
+
+fn macron(vowel: char) -> Option<char> {
+    LONG_VOWELS
+        .iter()
+        .find(|(_, short_vowel)| *short_vowel == vowel)
+        .map(|(long_vowel, _)| *long_vowel)
+}
+
+fn is_vowel_or_y(c: char) -> bool {
+    matches!(c, 'a' | 'i' | 'u' | 'e' | 'o' | 'y')
+}
+
+/// Consonants doubled by a small tsu, "nn" is ん.
+fn is_doubled_consonant(c: char) -> bool {
+    c.is_ascii_lowercase() && !is_vowel_or_y(c) && c != 'n'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Kanas sharing their rōmaji with another kana, they can't come back from rōmaji.
+    fn is_ambiguous(kana: &Kana) -> bool {
+        // ぢ and じ are "ji", づ and ず are "zu", を and お are "o", ウォ and ヲ are "wo"
+        matches!(kana, Kana::Dji | Kana::Dzu | Kana::Wo | Kana::Uo)
+    }
+
+    fn round_trip(text: &str, representation: &KanaRepresentation) {
+        let romaji = to_romaji(text);
+        assert_eq!(
+            to_kana(&romaji, representation),
+            text,
+            "{} was converted to {}",
+            text,
+            romaji
+        );
+    }
+
+    #[test]
+    fn kana_to_romaji() {
+        assert_eq!(to_romaji("さかな"), "sakana");
+        assert_eq!(to_romaji("テレビ"), "terebi");
+        assert_eq!(to_romaji("きょう"), "kyou");
+        assert_eq!(to_romaji("ちず"), "chizu");
+        assert_eq!(to_romaji("がっこう"), "gakkou");
+        assert_eq!(to_romaji("まっちゃ"), "matcha");
+        assert_eq!(to_romaji("ラーメン"), "rāmen");
+        assert_eq!(to_romaji("コーヒー"), "kōhī");
+        assert_eq!(to_romaji("きんえん"), "kin'en");
+        assert_eq!(to_romaji("ほんや"), "hon'ya");
+        assert_eq!(to_romaji("しんぶん"), "shinbun");
+        assert_eq!(to_romaji("あっ"), "axtsu");
+        assert_eq!(to_romaji("ファクス"), "fakusu");
+        assert_eq!(to_romaji("はなぢ"), "hanaji");
+        assert_eq!(to_romaji("つづく"), "tsuzuku");
+        assert_eq!(to_romaji("ウォッカ"), "wokka");
+        assert_eq!(to_romaji("テレビを見る"), "terebio見ru");
+        assert_eq!(to_romaji("ほんを"), "hon'o");
+    }
+
+    #[test]
+    fn romaji_to_kana() {
+        assert_eq!(to_hiragana("sakana"), "さかな");
+        assert_eq!(to_katakana("Terebi"), "テレビ");
+        assert_eq!(to_hiragana("gakkou"), "がっこう");
+        assert_eq!(to_hiragana("matcha"), "まっちゃ");
+        assert_eq!(to_katakana("rāmen"), "ラーメン");
+        assert_eq!(to_katakana("ra-men"), "ラーメン");
+        assert_eq!(to_hiragana("tōkyō"), "とうきょう");
+        assert_eq!(to_hiragana("kin'en"), "きんえん");
+        assert_eq!(to_hiragana("kinen"), "きねん");
+        assert_eq!(to_hiragana("onna"), "おんな");
+        assert_eq!(to_hiragana("sya"), "しゃ");
+        // the Hepburn spellings shared by two kanas are the common kana
+        assert_eq!(to_hiragana("hanaji"), "はなじ");
+        assert_eq!(to_hiragana("tsuzuku"), "つずく");
+        assert_eq!(to_katakana("wokka"), "ヲッカ");
+        assert_eq!(to_hiragana("o"), "お");
+        assert_eq!(to_hiragana("k"), "k");
+        assert_eq!(to_hiragana("kak"), "かk");
+        assert_eq!(to_hiragana("sakana desu!"), "さかな です!");
+    }
+
+    #[test]
+    fn kana_to_the_other_script() {
+        assert_eq!(to_hiragana("ラーメン"), "らーめん");
+        assert_eq!(to_katakana("がっこう"), "ガッコウ");
+        assert_eq!(to_katakana("ティー"), "ティー");
+        assert_eq!(to_hiragana("パン desu"), "ぱん です");
+    }
+
+    #[test]
+    fn kunrei_and_nihon() {
+        let kunrei = Romanization::Kunrei;
+        assert_eq!(to_romaji_in("しんぶんし", &kunrei), "sinbunsi");
+        assert_eq!(to_romaji_in("まっちゃ", &kunrei), "mattya");
+        assert_eq!(to_romaji_in("つづく", &kunrei), "tuzuku");
+        assert_eq!(to_romaji_in("ほんを", &kunrei), "hon'o");
+        let nihon = Romanization::Nihon;
+        assert_eq!(to_romaji_in("つづく", &nihon), "tuduku");
+        assert_eq!(to_romaji_in("ほんを", &nihon), "honwo");
+        let hiragana = KanaRepresentation::Hiragana;
+        assert_eq!(to_kana_in("mattya", &hiragana, &kunrei), "まっちゃ");
+        assert_eq!(to_kana_in("ti", &hiragana, &kunrei), "ち");
+        assert_eq!(
+            to_kana_in("ti", &KanaRepresentation::Katakana, &Romanization::Hepburn),
+            "ティ"
+        );
+        // other spellings are still understood
+        assert_eq!(to_kana_in("shinbunshi", &hiragana, &kunrei), "しんぶんし");
+    }
+
+    #[test]
+    fn round_trip_every_kana() {
+        for representation in [KanaRepresentation::Hiragana, KanaRepresentation::Katakana] {
+            for kana in Kana::iter().filter(|kana| !is_ambiguous(kana)) {
+                let glyph = kana.to_representation(&representation);
+                round_trip(glyph, &representation);
+                round_trip(
+                    &format!("{}{}", small_tsu(&representation), glyph),
+                    &representation,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_every_pair_of_kanas() {
+        let representation = KanaRepresentation::Katakana;
+        let kanas: Vec<Kana> = Kana::iter().filter(|kana| !is_ambiguous(kana)).collect();
+        for first in &kanas {
+            let first = first.to_representation(&representation);
+            for second in &kanas {
+                let second = second.to_representation(&representation);
+                round_trip(&format!("{}{}", first, second), &representation);
+                round_trip(&format!("{}ッ{}", first, second), &representation);
+                round_trip(&format!("{}ー{}", first, second), &representation);
+            }
+        }
+    }
+}
@@ -2,12 +2,15 @@ use crate::app::{App, IPage};
 use crate::config::Config;
 use crate::event::EventHandler;
 use crate::AppResult;
-use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::event::{
+    DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture,
+};
 use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
 use ratatui::backend::Backend;
 use ratatui::layout::{Alignment, Constraint, Flex, Layout, Margin, Rect};
 use ratatui::style::{Color, Style, Stylize};
-use ratatui::widgets::{Block, BorderType, Borders};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Borders, Paragraph, Wrap};
 use ratatui::{Frame, Terminal};
 use std::io;
 use std::panic;
@@ -34,7 +37,12 @@ impl<B: Backend> Tui<B> {
     /// It enables the raw mode and sets terminal properties.
     pub fn init(&mut self) -> AppResult<()> {
         terminal::enable_raw_mode()?;
-        crossterm::execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+        crossterm::execute!(
+            io::stdout(),
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableFocusChange
+        )?;
 
         // Define a custom panic hook to reset the terminal properties.
         // This way, you won't have your terminal messed up if an unexpected error happens.
@@ -52,17 +60,14 @@ impl<B: Backend> Tui<B> {
     /// Draw the terminal interface by [`rendering`] the widgets.
     /// Corresponding to each page render function.
     pub fn draw(&mut self, app: &mut App, config: &Config) -> AppResult<()> {
-        self.terminal.draw(|frame| {
-            let main_area = flex(
-                frame.area(),
-                (Flex::Center, Constraint::Max(32)),
-                (Flex::Center, Constraint::Max(20)),
-            );
-            render_header_block(frame, main_area);
-            // to prevent overlap with the header block
-            let inner_main_area = main_area.inner(Margin::new(1, 1));
-            app.current_page.render(frame, inner_main_area, config);
-        })?;
+        self.terminal.draw(|frame| render(frame, app, config))?;
+        Ok(())
+    }
+
+    /// Resize the terminal interface, everything is drawn again on the next draw.
+    pub fn resize(&mut self, width: u16, height: u16) -> AppResult<()> {
+        self.terminal.resize(Rect::new(0, 0, width, height))?;
+        self.terminal.clear()?;
         Ok(())
     }
 
@@ -72,7 +77,12 @@ impl<B: Backend> Tui<B> {
     /// the terminal properties if unexpected errors occur.
     fn reset() -> AppResult<()> {
         terminal::disable_raw_mode()?;
-        crossterm::execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+        crossterm::execute!(
+            io::stdout(),
+            LeaveAlternateScreen,
+            DisableMouseCapture,
+            DisableFocusChange
+        )?;
         Ok(())
     }
 
@@ -86,6 +96,146 @@ impl<B: Backend> Tui<B> {
     }
 }
 
+/// Smallest size of the box the pages are shown in, with its border.
+const MAIN_WIDTH: u16 = 32;
+const MAIN_HEIGHT: u16 = 20;
+/// The main box grows with the terminal until this size.
+const MAX_MAIN_WIDTH: u16 = 60;
+const MAX_MAIN_HEIGHT: u16 = 30;
+/// Size of the pages, inside the border of the main box.
+const PAGE_WIDTH: u16 = MAIN_WIDTH - 2;
+const PAGE_HEIGHT: u16 = MAIN_HEIGHT - 2;
+/// Width of the panels shown on each side of the main box, with their border.
+const PANEL_WIDTH: u16 = 26;
+
+/// How the interface is laid out, depending on the size of the terminal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScreenSize {
+    /// The terminal is smaller than the main box, the page is shown without its border.
+    /// Pages without a [compact layout](IPage::has_compact_layout) need the size inside it.
+    Compact,
+    /// The main box, as large as the terminal allows.
+    Regular,
+    /// The main box at its largest, with side panels on its left and on its right.
+    Large,
+}
+
+impl ScreenSize {
+    pub fn of(area: Rect) -> Self {
+        if area.width < MAIN_WIDTH || area.height < MAIN_HEIGHT {
+            Self::Compact
+        } else if area.width >= MAX_MAIN_WIDTH + 2 * (PANEL_WIDTH + 1) {
+            Self::Large
+        } else {
+            Self::Regular
+        }
+    }
+}
+
+/// Render the current page, laid out for the size of the terminal.
+pub fn render(frame: &mut Frame, app: &mut App, config: &Config) {
+    let area = frame.area();
+    app.is_page_shown = true;
+    let main_area = match ScreenSize::of(area) {
+        ScreenSize::Compact if app.current_page.has_compact_layout() => {
+            app.current_page.render(frame, area, config);
+            return;
+        }
+        ScreenSize::Compact if area.width >= PAGE_WIDTH && area.height >= PAGE_HEIGHT => {
+            let page_area = flex(
+                area,
+                (Flex::Center, Constraint::Length(PAGE_WIDTH)),
+                (Flex::Center, Constraint::Length(PAGE_HEIGHT)),
+            );
+            app.current_page.render(frame, page_area, config);
+            return;
+        }
+        ScreenSize::Compact => {
+            app.is_page_shown = false;
+            render_too_small(frame, area);
+            return;
+        }
+        ScreenSize::Regular | ScreenSize::Large => {
+            let (main_area, panel_areas) = main_layout(area);
+            if let Some(panel_areas) = panel_areas {
+                app.current_page
+                    .render_side_panels(frame, panel_areas, config);
+            }
+            main_area
+        }
+    };
+    render_header_block(frame, main_area);
+    // to prevent overlap with the header block
+    let inner_main_area = main_area.inner(Margin::new(1, 1));
+    app.current_page.render(frame, inner_main_area, config);
+}
+
+/// The main box at the center of the terminal, as large as it allows,
+/// and the areas of the side panels when they are shown (see [`ScreenSize::Large`]).
+fn main_layout(area: Rect) -> (Rect, Option<[Rect; 2]>) {
+    let height = (
+        Flex::Center,
+        Constraint::Length(area.height.min(MAX_MAIN_HEIGHT)),
+    );
+    match ScreenSize::of(area) {
+        ScreenSize::Large => {
+            let [left_area, main_area, right_area] = Layout::horizontal([
+                Constraint::Length(PANEL_WIDTH),
+                Constraint::Length(MAX_MAIN_WIDTH),
+                Constraint::Length(PANEL_WIDTH),
+            ])
+            .flex(Flex::Center)
+            .spacing(1)
+            .areas(flex(area, (Flex::Center, Constraint::Fill(1)), height));
+            (main_area, Some([left_area, right_area]))
+        }
+        _ => {
+            let width = (
+                Flex::Center,
+                Constraint::Length(area.width.min(MAX_MAIN_WIDTH)),
+            );
+            (flex(area, width, height), None)
+        }
+    }
+}
+
+/// A side panel with its title, returns the area inside its border.
+pub fn render_panel_block(frame: &mut Frame, area: Rect, title: &str) -> Rect {
+    let block = Block::default()
+        .title(format!(" {} ", title))
+        .title_alignment(Alignment::Center)
+        .title_style(Style::default().gray())
+        .borders(Borders::all())
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::DarkGray));
+    let inner_area = block.inner(area);
+    frame.render_widget(block, area);
+    inner_area
+}
+
+/// Shown instead of a page that doesn't fit in the terminal.
+fn render_too_small(frame: &mut Frame, area: Rect) {
+    let lines = Vec::from([
+        Line::from("The terminal is too small.").bold(),
+        Line::from(format!("Resize it to {}×{}.", PAGE_WIDTH, PAGE_HEIGHT)).dim(),
+    ]);
+    // roughly, the lines are wrapped on words
+    let height: usize = lines
+        .iter()
+        .map(|line| line.width().div_ceil(usize::from(area.width.max(1))))
+        .sum();
+    let notice = Paragraph::new(lines).centered().wrap(Wrap { trim: true });
+    let height = u16::try_from(height).unwrap_or(u16::MAX).min(area.height);
+    frame.render_widget(
+        notice,
+        flex(
+            area,
+            (Flex::Center, Constraint::Fill(1)),
+            (Flex::Center, Constraint::Length(height)),
+        ),
+    );
+}
+
 fn render_header_block(frame: &mut Frame, main_area: Rect) {
     let block = Block::default()
         .title(" kana-tui ")
@@ -105,3 +255,37 @@ pub fn flex(area: Rect, horizontal: (Flex, Constraint), vertical: (Flex, Constra
     let [area] = Layout::vertical([vertical.1]).flex(vertical.0).areas(area);
     area
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::BigKana;
+
+    #[test]
+    fn layout_breakpoints() {
+        let size = |width, height| ScreenSize::of(Rect::new(0, 0, width, height));
+        assert_eq!(size(31, 40), ScreenSize::Compact);
+        assert_eq!(size(100, 19), ScreenSize::Compact);
+        assert_eq!(size(32, 20), ScreenSize::Regular);
+        assert_eq!(size(113, 40), ScreenSize::Regular);
+        assert_eq!(size(114, 20), ScreenSize::Large);
+
+        // the main box grows with the terminal
+        let (main_area, panel_areas) = main_layout(Rect::new(0, 0, 32, 20));
+        assert_eq!((main_area, panel_areas), (Rect::new(0, 0, 32, 20), None));
+        let (main_area, panel_areas) = main_layout(Rect::new(0, 0, 80, 24));
+        assert_eq!((main_area, panel_areas), (Rect::new(10, 0, 60, 24), None));
+        let (main_area, panel_areas) = main_layout(Rect::new(0, 0, 200, 50));
+        assert_eq!(main_area, Rect::new(70, 10, 60, 30));
+        assert_eq!(
+            panel_areas,
+            Some([Rect::new(43, 10, 26, 30), Rect::new(131, 10, 26, 30)])
+        );
+
+        // a long word in big kanas fits in a usual terminal
+        let page_area = main_layout(Rect::new(0, 0, 80, 24))
+            .0
+            .inner(Margin::new(1, 1));
+        assert!(BigKana::new("ひこうき").unwrap().width() <= page_area.width);
+    }
+}
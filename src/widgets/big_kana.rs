@@ -0,0 +1,184 @@
+use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+use std::{collections::HashMap, sync::LazyLock};
+
+/// Bitmaps of every kana, embedded at build time.
+static GLYPHS: LazyLock<HashMap<char, Glyph>> =
+    LazyLock::new(|| parse_glyphs(include_str!("kana_glyphs.txt")));
+
+/// The dots of a glyph, row by row.
+#[derive(Debug, Clone, PartialEq)]
+struct Glyph {
+    rows: Vec<Vec<bool>>,
+}
+
+impl Glyph {
+    fn width(&self) -> u16 {
+        self.rows.first().map_or(0, |row| row.len() as u16)
+    }
+
+    fn height(&self) -> u16 {
+        self.rows.len() as u16
+    }
+
+    fn dot(&self, x: u16, y: u16) -> bool {
+        self.rows
+            .get(usize::from(y))
+            .and_then(|row| row.get(usize::from(x)))
+            .copied()
+            .unwrap_or(false)
+    }
+}
+
+/// Kanas drawn with blocks, each cell shows two dots of the bitmap (one above the other).
+///
+/// Small kanas (e.g. the ょ of きょ) are aligned on the bottom of the others.
+#[derive(Debug, Clone)]
+pub struct BigKana {
+    glyphs: Vec<&'static Glyph>,
+    /// Each dot of the bitmaps is drawn as a square of this many dots.
+    scale: u16,
+}
+
+impl BigKana {
+    /// Returns `None` if one of the characters has no big glyph.
+    pub fn new(text: &str) -> Option<Self> {
+        let glyphs = text
+            .chars()
+            .map(|c| GLYPHS.get(&c))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self { glyphs, scale: 1 })
+    }
+
+    /// Draw the glyphs larger, to fill a large terminal.
+    pub fn scaled(mut self, scale: u16) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Glyphs are one column apart (scaled too).
+    pub fn width(&self) -> u16 {
+        let glyphs_width: u16 = self.glyphs.iter().map(|glyph| glyph.width()).sum();
+        (glyphs_width + (self.glyphs.len() as u16).saturating_sub(1)) * self.scale
+    }
+
+    pub fn height(&self) -> u16 {
+        let dots_height = self
+            .glyphs
+            .iter()
+            .map(|glyph| glyph.height())
+            .max()
+            .unwrap_or(0);
+        (dots_height * self.scale).div_ceil(2)
+    }
+}
+
+impl Widget for BigKana {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let scale = self.scale;
+        let dots_height = self.height() * 2;
+        let mut x = area.x;
+        for glyph in &self.glyphs {
+            // aligned on the bottom
+            let top = dots_height - glyph.height() * scale;
+            for column in 0..glyph.width() * scale {
+                for row in 0..self.height() {
+                    let position = (x + column, area.y + row);
+                    if !area.contains(position.into()) {
+                        continue;
+                    }
+                    let dot = |y: u16| y >= top && glyph.dot(column / scale, (y - top) / scale);
+                    let symbol = match (dot(row * 2), dot(row * 2 + 1)) {
+                        (true, true) => "\u{2588}",
+                        (true, false) => "\u{2580}",
+                        (false, true) => "\u{2584}",
+                        (false, false) => continue,
+                    };
+                    buf[position].set_symbol(symbol);
+                }
+            }
+            x += (glyph.width() + 1) * scale;
+        }
+    }
+}
+
+/// Each glyph is its character on a line, then its rows of dots (`#`) until an empty line.
+/// Lines starting with `//` are comments.
+fn parse_glyphs(data: &str) -> HashMap<char, Glyph> {
+    let mut glyphs = HashMap::new();
+    let mut lines = data.lines().filter(|line| !line.starts_with("//"));
+    while let Some(line) = lines.next() {
+        let Some(c) = line.chars().next() else {
+            continue;
+        };
+        let rows = lines
+            .by_ref()
+            .take_while(|row| !row.is_empty())
+            .map(|row| row.chars().map(|dot| dot == '#').collect())
+            .collect();
+        glyphs.insert(c, Glyph { rows });
+    }
+    glyphs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::kana::Kana;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn every_kana_has_a_big_glyph() {
+        for kana in Kana::iter() {
+            let katakana = BigKana::new(kana.to_katakana());
+            assert!(
+                katakana.is_some(),
+                "{} has no big glyph",
+                kana.to_katakana()
+            );
+            if !kana.is_extended_katakana() {
+                let hiragana = BigKana::new(kana.to_hiragana());
+                assert!(
+                    hiragana.is_some(),
+                    "{} has no big glyph",
+                    kana.to_hiragana()
+                );
+            }
+        }
+        assert!(BigKana::new("a").is_none());
+    }
+
+    #[test]
+    fn size_of_big_kanas() {
+        let kana = BigKana::new("か").unwrap();
+        assert_eq!((kana.width(), kana.height()), (12, 6));
+        let yoon = BigKana::new("きょ").unwrap();
+        assert_eq!((yoon.width(), yoon.height()), (21, 6));
+        let yoon = yoon.scaled(2);
+        assert_eq!((yoon.width(), yoon.height()), (42, 12));
+    }
+
+    #[test]
+    fn scaled_dots() {
+        let render = |kana: BigKana| {
+            let area = Rect::new(0, 0, kana.width(), kana.height());
+            let mut buf = Buffer::empty(area);
+            kana.render(area, &mut buf);
+            buf
+        };
+        let kana = render(BigKana::new("か").unwrap());
+        let scaled = render(BigKana::new("か").unwrap().scaled(2));
+        for y in 0..6 {
+            for x in 0..12 {
+                // each dot of a cell fills a cell of the scaled glyph
+                let symbol = kana[(x, y)].symbol();
+                let top_dot = matches!(symbol, "\u{2588}" | "\u{2580}");
+                let bottom_dot = matches!(symbol, "\u{2588}" | "\u{2584}");
+                assert_eq!(top_dot, scaled[(x * 2, y * 2)].symbol() == "\u{2588}");
+                assert_eq!(
+                    bottom_dot,
+                    scaled[(x * 2, y * 2 + 1)].symbol() == "\u{2588}"
+                );
+            }
+        }
+    }
+}
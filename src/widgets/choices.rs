@@ -0,0 +1,98 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Position, Rect},
+    style::{Color, Stylize},
+    text::Span,
+    widgets::{StatefulWidget, Widget},
+};
+
+/// A grid of choices, each one can be picked with its number.
+#[derive(Debug)]
+pub struct Choices<'a> {
+    labels: Vec<&'a str>,
+    columns: usize,
+}
+
+impl<'a> Choices<'a> {
+    pub fn new(labels: Vec<&'a str>, columns: usize) -> Self {
+        Self {
+            labels,
+            columns: columns.max(1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ChoicesState {
+    pub current_choice: usize,
+    /// Where each choice was rendered, updated on render.
+    pub areas: Vec<Rect>,
+}
+
+impl ChoicesState {
+    /// The choice rendered at this position, if any.
+    pub fn choice_at(&self, position: Position) -> Option<usize> {
+        self.areas.iter().position(|area| area.contains(position))
+    }
+
+    pub fn next_choice(&mut self, count: usize) {
+        self.current_choice = (self.current_choice + 1) % count.max(1);
+    }
+
+    pub fn previous_choice(&mut self, count: usize) {
+        self.current_choice = (self.current_choice + count.max(1) - 1) % count.max(1);
+    }
+
+    pub fn choice_below(&mut self, count: usize, columns: usize) {
+        if self.current_choice + columns < count {
+            self.current_choice += columns;
+        }
+    }
+
+    pub fn choice_above(&mut self, columns: usize) {
+        if self.current_choice >= columns {
+            self.current_choice -= columns;
+        }
+    }
+}
+
+impl StatefulWidget for Choices<'_> {
+    type State = ChoicesState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        state.areas.clear();
+        let rows_count = self.labels.len().div_ceil(self.columns);
+        // one empty line between each row
+        let rows = Layout::vertical((0..rows_count * 2).map(|_| Constraint::Length(1)))
+            .split(area)
+            .iter()
+            .step_by(2)
+            .copied()
+            .collect::<Vec<Rect>>();
+
+        for (row_index, (row, row_labels)) in rows
+            .iter()
+            .zip(self.labels.chunks(self.columns))
+            .enumerate()
+        {
+            let cells = Layout::horizontal((0..self.columns).map(|_| Constraint::Fill(1)))
+                .spacing(1)
+                .split(*row);
+            for (column_index, (cell, label)) in cells.iter().zip(row_labels).enumerate() {
+                let i = row_index * self.columns + column_index;
+                let (fg, bg) = if i == state.current_choice {
+                    (Color::Black, Color::LightRed)
+                } else {
+                    (Color::White, Color::DarkGray)
+                };
+                Span::from(format!(" {} {} ", i + 1, label))
+                    .fg(fg)
+                    .bg(bg)
+                    .bold()
+                    .into_centered_line()
+                    .render(*cell, buf);
+                state.areas.push(*cell);
+            }
+        }
+    }
+}
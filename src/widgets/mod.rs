@@ -1,5 +1,9 @@
+mod big_kana;
 mod button;
+mod choices;
 mod menu;
 
+pub use big_kana::*;
 pub use button::*;
+pub use choices::*;
 pub use menu::*;